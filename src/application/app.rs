@@ -1,40 +1,367 @@
-use core::time::Duration;
-use std::fs;
+use std::{
+    error::Error,
+    fs::{self, File},
+    io::{self, BufWriter, IsTerminal, Write},
+    process,
+};
 
 use crate::{
     domain::{
-        game::play,
-        grid::{size::Size, Grid},
+        analysis::{analyze, Periodicity},
+        cell::{alphabet::Alphabet, coordinates::Coordinates},
+        game::{play, play_interactively, record, PlayError},
+        grid::{
+            functions::{overlap::overlap, transform::crop},
+            placement::Placement,
+            size::Size,
+            topology::Topology,
+            Grid,
+        },
+        outcome::SimulationOutcome,
+        output::{image::ImageStyle, snapshot::ImageFormat},
+        pattern::{parse_pattern, write_pattern, Pattern, PatternFormat},
         settings::Settings,
+        stability::MAX_PERIOD,
+        statistics::{write_statistics, StatisticsFormat},
+    },
+    infrastructure::{
+        animation::GifRecorder, console::Console, snapshot::SnapshotRecorder,
+        terminal::RawTerminal, thread::Sleeper,
+    },
+    ui::{
+        console::{
+            parse_args, print_analysis, print_arguments_error, print_export_done,
+            print_export_error, print_images_done, print_invalid_pattern_error,
+            print_pattern_does_not_fit_error, print_simulation_stats, print_stop_reason,
+            print_terminal_error, print_unbounded_b0_rule_error, Arguments, Command,
+        },
+        editor::{edit, EditorExit},
+        help::{print_usage, print_version},
     },
-    infrastructure::{console::Console, thread::Sleeper},
-    ui::console::{parse_args, Arguments},
 };
 
+/// Empty cells around the pattern when the grid size is inferred from it.
+const PATTERN_MARGIN: usize = 10;
+
 // todo: add unit tests for `app::run`.
 
 #[must_use]
-pub fn run(args: &Vec<String>) -> String {
-    let args = parse_args(args);
-
-    play(
-        &setup_settings(&args),
-        &build_pattern(&args.pattern_file_path),
-        &Console::new(),
-        &Sleeper::default(),
+pub fn run(args: &[String]) -> SimulationOutcome {
+    let args = match parse_args(args) {
+        Ok(Command::Run(arguments)) => *arguments,
+        Ok(Command::Help) => {
+            print_usage();
+            process::exit(0);
+        }
+        Ok(Command::Version) => {
+            print_version();
+            process::exit(0);
+        }
+        Err(error) => {
+            print_arguments_error(&error);
+            print_usage();
+            process::exit(1);
+        }
+    };
+    let pattern = match build_pattern(&args.pattern_file_path, args.alphabet) {
+        Ok(pattern) => pattern,
+        Err(error) => {
+            print_invalid_pattern_error(&args.pattern_file_path, &error.to_string());
+            process::exit(1);
+        }
+    };
+
+    let settings = setup_settings(&args, &pattern);
+
+    let (settings, grid) = if args.edit {
+        edit_pattern(&args, settings, &pattern)
+    } else {
+        (settings, pattern.grid.clone())
+    };
+
+    // Redrawing only the changed cells needs a terminal, pipes get every frame
+    let full_redraw = args.full_redraw || !io::stdout().is_terminal();
+
+    let result = if let Some(gif_path) = &args.gif_path {
+        export_gif(&args, &settings, &grid, gif_path)
+    } else if let Some(image_path) = &args.image_path {
+        export_images(&args, &settings, &grid, image_path)
+    } else if args.interactive {
+        let terminal = if full_redraw {
+            raw_terminal()
+        } else {
+            raw_terminal().with_incremental_rendering()
+        };
+        play_interactively(&settings, &grid, &terminal, &Sleeper::default(), &terminal)
+    } else {
+        let console = if full_redraw {
+            Console::new()
+        } else {
+            Console::new().with_incremental_rendering()
+        };
+        play(&settings, &grid, &console, &Sleeper::default())
+    };
+
+    match result {
+        Ok(outcome) => {
+            if args.until_stable {
+                print_stop_reason(&outcome.stop_reason, outcome.last_generation());
+            }
+            if args.interactive {
+                print_simulation_stats(&outcome);
+            }
+            if let Some(gif_path) = &args.gif_path {
+                print_export_done(gif_path, outcome.generations);
+            }
+            if let Some(stats_path) = &args.stats_path {
+                save_statistics(&outcome, stats_path);
+            }
+            if args.analyze {
+                print_analysis(analyze_final_grid(&settings, &outcome).as_ref(), MAX_PERIOD);
+            }
+            outcome
+        }
+        Err(PlayError::PatternDoesNotFit(error)) => {
+            print_pattern_does_not_fit_error(&error.to_string());
+            process::exit(1);
+        }
+        Err(PlayError::UnboundedB0Rule(error)) => {
+            print_unbounded_b0_rule_error(&error.to_string());
+            process::exit(1);
+        }
+    }
+}
+
+/// The pattern is edited on the background grid. The edited grid is played
+/// as it is, so it takes the place of the background grid.
+fn edit_pattern(args: &Arguments, mut settings: Settings, pattern: &Pattern) -> (Settings, Grid) {
+    let pattern_position = match settings
+        .placement
+        .position(&settings.back_grid_size, &pattern.grid.size())
+    {
+        Ok(position) => position,
+        Err(error) => {
+            print_pattern_does_not_fit_error(&error.to_string());
+            process::exit(1);
+        }
+    };
+
+    let back_grid = Grid::of_dead_cells(
+        settings.back_grid_size.rows,
+        settings.back_grid_size.columns,
     )
+    .with_topology(settings.topology);
+
+    let save_path = args
+        .save_path
+        .clone()
+        .unwrap_or_else(|| args.pattern_file_path.clone());
+
+    let exit = {
+        let terminal = raw_terminal();
+        edit(
+            overlap(&back_grid, &pattern.grid, &pattern_position),
+            &settings.alphabet,
+            &save_path,
+            |grid| save_pattern(grid, pattern, &save_path, settings.alphabet),
+            &terminal,
+            &terminal,
+        )
+    };
+
+    match exit {
+        EditorExit::Run(grid) => {
+            settings.back_grid_size = grid.size();
+            settings.placement = Placement::Offset(Coordinates::new(0, 0));
+            (settings, grid)
+        }
+        EditorExit::Quit => process::exit(0),
+    }
 }
 
-fn setup_settings(args: &Arguments) -> Settings {
+/// Only the live cells are saved, with the metadata of the original pattern.
+/// The format is the one for the file extension, patterns are not saved to
+/// files with an unknown one.
+fn save_pattern(grid: &Grid, pattern: &Pattern, path: &str, alphabet: Alphabet) -> io::Result<()> {
+    let format = PatternFormat::for_saving(path, &fs::read_to_string(path).unwrap_or_default())
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "unknown pattern file extension, use .txt, .cells, .rle, .lif or .life",
+            )
+        })?;
+
+    let edited_pattern = Pattern {
+        grid: crop(grid),
+        ..pattern.clone()
+    };
+
+    fs::write(path, write_pattern(&edited_pattern, format, &alphabet))
+}
+
+/// The game is played with no terminal and every generation is saved as a
+/// frame of the GIF.
+fn export_gif(
+    args: &Arguments,
+    settings: &Settings,
+    grid: &Grid,
+    path: &str,
+) -> Result<SimulationOutcome, PlayError> {
+    let exit_with_error = |error: io::Error| -> ! {
+        print_export_error(path, &error.to_string());
+        process::exit(1);
+    };
+
+    let file = File::create(path).unwrap_or_else(|error| exit_with_error(error));
+
+    let recorder = GifRecorder::new(
+        BufWriter::new(file),
+        image_style(args),
+        settings.generation_lifetime,
+    );
+    let recorder = if args.looping {
+        recorder
+    } else {
+        recorder.without_looping()
+    };
+
+    let outcome = record(settings, grid, &recorder)?;
+
+    if let Err(error) = recorder.finish().and_then(|mut writer| writer.flush()) {
+        exit_with_error(error);
+    }
+
+    Ok(outcome)
+}
+
+/// The game is played with no terminal and the chosen generations are saved
+/// as numbered images. The game stops once all of them are saved.
+fn export_images(
+    args: &Arguments,
+    settings: &Settings,
+    grid: &Grid,
+    path: &str,
+) -> Result<SimulationOutcome, PlayError> {
+    let settings = Settings {
+        generations: args
+            .snapshots
+            .generations_needed()
+            .map_or(settings.generations, |generations| {
+                generations.min(settings.generations)
+            }),
+        ..settings.clone()
+    };
+
+    let recorder = SnapshotRecorder::new(
+        path,
+        ImageFormat::from_file_name(path).expect("the image format should have been checked"),
+        image_style(args),
+        args.snapshots,
+        settings.generations.saturating_sub(1),
+    );
+
+    let outcome = record(&settings, grid, &recorder)?;
+
+    match recorder.finish() {
+        Ok(written) if written.is_empty() => {
+            print_export_error(path, "the game ended before the generation to save");
+            process::exit(1);
+        }
+        Ok(written) => print_images_done(&written),
+        Err(error) => {
+            print_export_error(path, &error.to_string());
+            process::exit(1);
+        }
+    }
+
+    Ok(outcome)
+}
+
+/// On an unbounded universe, only the cells inside the viewport are
+/// analyzed, as if nothing was outside.
+fn analyze_final_grid(settings: &Settings, outcome: &SimulationOutcome) -> Option<Periodicity> {
+    let topology = if settings.unbounded {
+        Topology::Plane
+    } else {
+        settings.topology
+    };
+
+    analyze(
+        &outcome.final_grid.clone().with_topology(topology),
+        &settings.rule,
+        MAX_PERIOD,
+    )
+}
+
+/// The format is the one for the file extension.
+fn save_statistics(outcome: &SimulationOutcome, path: &str) {
+    let format = StatisticsFormat::from_file_name(path)
+        .expect("the statistics format should have been checked");
+
+    if let Err(error) = fs::write(path, write_statistics(&outcome.statistics, format)) {
+        print_export_error(path, &error.to_string());
+        process::exit(1);
+    }
+}
+
+fn image_style(args: &Arguments) -> ImageStyle {
+    ImageStyle {
+        cell_size: args.cell_size as usize,
+        grid_lines: args.grid_lines,
+        ruler: args.ruler,
+        live_colour: args.live_colour,
+        dead_colour: args.dead_colour,
+        line_colour: args.line_colour,
+    }
+}
+
+fn raw_terminal() -> RawTerminal {
+    match RawTerminal::new() {
+        Ok(terminal) => terminal,
+        Err(error) => {
+            print_terminal_error(&error.to_string());
+            process::exit(1);
+        }
+    }
+}
+
+/// Rows and columns omitted in the arguments are inferred from the pattern
+/// size, leaving some empty cells around it.
+fn setup_settings(args: &Arguments, pattern: &Pattern) -> Settings {
+    let inferred_size = |pattern_size: usize| pattern_size + 2 * PATTERN_MARGIN;
+
     Settings {
-        back_grid_size: Size::new(args.rows as usize, args.columns as usize),
+        back_grid_size: Size::new(
+            args.rows
+                .map_or_else(|| inferred_size(pattern.grid.rows()), |rows| rows as usize),
+            args.columns.map_or_else(
+                || inferred_size(pattern.grid.columns()),
+                |columns| columns as usize,
+            ),
+        ),
         generations: args.generations,
-        generation_lifetime: Duration::from_secs(args.generation_lifetime.into()),
+        generation_lifetime: args.generation_lifetime,
+        rule: args
+            .rule
+            .clone()
+            .or_else(|| pattern.rule.clone())
+            .unwrap_or_default(),
+        topology: args.topology,
+        unbounded: args.unbounded,
+        threads: args.threads as usize,
+        alphabet: args.alphabet,
+        placement: args.placement,
+        until_stable: args.until_stable,
+        render_mode: args.render_mode,
+        colour_by_age: args.colour_by_age,
+        trail: args.trail,
+        status_line: args.status_line,
+        statistics: args.stats_path.is_some(),
     }
 }
 
-fn build_pattern(pattern_file_path: &str) -> Grid {
-    let text_pattern = fs::read_to_string(pattern_file_path)
-        .expect("should have been able to read the file containing the pattern");
-    text_pattern.parse().expect("invalid text pattern")
+fn build_pattern(pattern_file_path: &str, alphabet: Alphabet) -> Result<Pattern, Box<dyn Error>> {
+    let text_pattern = fs::read_to_string(pattern_file_path)?;
+    let format = PatternFormat::of_file(pattern_file_path, &text_pattern);
+    Ok(parse_pattern(&text_pattern, format, &alphabet)?)
 }
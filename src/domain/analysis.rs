@@ -0,0 +1,313 @@
+use std::{fmt, fmt::Write};
+
+use super::{
+    cell::coordinates::Coordinates,
+    grid::{functions::next_generation::next_generation, Grid},
+    rule::Rule,
+    universe::position::Position,
+};
+
+/// What a periodic pattern is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatternKind {
+    /// It does not change.
+    StillLife,
+    /// It goes back to the same cells after the period.
+    Oscillator,
+    /// It goes back to the same shape, somewhere else, after the period.
+    Spaceship,
+}
+
+/// How a pattern repeats itself: after `period` generations it is the same
+/// pattern moved `dx` columns to the right and `dy` rows down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Periodicity {
+    pub period: usize,
+    pub dx: i64,
+    pub dy: i64,
+}
+
+impl Periodicity {
+    #[must_use]
+    pub fn kind(&self) -> PatternKind {
+        match (self.dx, self.dy) {
+            (0, 0) if self.period == 1 => PatternKind::StillLife,
+            (0, 0) => PatternKind::Oscillator,
+            _ => PatternKind::Spaceship,
+        }
+    }
+
+    /// Speed in the usual notation, where `c` is one cell per generation,
+    /// followed by the direction: `c/4 diagonal` for the glider or
+    /// `c/2 orthogonal` for the lightweight spaceship. None for patterns
+    /// that do not move.
+    #[must_use]
+    pub fn speed(&self) -> Option<String> {
+        let distance = self.dx.unsigned_abs().max(self.dy.unsigned_abs());
+        if distance == 0 {
+            return None;
+        }
+
+        let period = self.period as u64;
+        let divisor = gcd(distance, period);
+        let (cells, generations) = (distance / divisor, period / divisor);
+
+        let mut speed = if cells == 1 {
+            "c".to_owned()
+        } else {
+            format!("{cells}c")
+        };
+        if generations != 1 {
+            write!(speed, "/{generations}").unwrap();
+        }
+
+        let direction = if self.dx == 0 || self.dy == 0 {
+            "orthogonal"
+        } else if self.dx.abs() == self.dy.abs() {
+            "diagonal"
+        } else {
+            "oblique"
+        };
+
+        Some(format!("{speed} {direction}"))
+    }
+}
+
+impl fmt::Display for Periodicity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind() {
+            PatternKind::StillLife => write!(f, "still life"),
+            PatternKind::Oscillator => write!(f, "oscillator with period {}", self.period),
+            PatternKind::Spaceship => write!(
+                f,
+                "spaceship with period {}, displacement ({}, {}) and speed {}",
+                self.period,
+                self.dx,
+                self.dy,
+                self.speed().unwrap_or_default()
+            ),
+        }
+    }
+}
+
+/// It finds the shortest period, up to `max_period` generations, after
+/// which the grid is the same or the same moved somewhere else.
+///
+/// Moved cells follow the grid topology, so on a torus a spaceship can
+/// cross the edges. Grids without live cells, patterns that die out and
+/// patterns with a longer period are not periodic.
+#[must_use]
+pub fn analyze(grid: &Grid, rule: &Rule, max_period: usize) -> Option<Periodicity> {
+    let live_cells = live_cells(grid);
+    if live_cells.is_empty() {
+        return None;
+    }
+
+    let mut generation = grid.clone();
+
+    for period in 1..=max_period {
+        generation = next_generation(&generation, rule);
+
+        if generation.population() != live_cells.len() {
+            continue;
+        }
+
+        if let Some((dx, dy)) = displacement(grid, &live_cells, &generation) {
+            return Some(Periodicity { period, dx, dy });
+        }
+    }
+
+    None
+}
+
+/// Displacement that moves all the live cells of the grid onto the live
+/// cells of the later generation, the shortest one if there are several.
+/// Both grids have the same population.
+fn displacement(grid: &Grid, live_cells: &[Coordinates], later: &Grid) -> Option<(i64, i64)> {
+    let anchor = Position::from(&live_cells[0]);
+
+    let mut candidates: Vec<(i64, i64)> = live_cells_iter(later)
+        .flat_map(|cell| {
+            let cell = Position::from(&cell);
+            let dx = cell.column - anchor.column;
+            let dy = cell.row - anchor.row;
+            // On stitched edges, the shortest way may cross them
+            [
+                (dx, dy),
+                (shortest(dx, grid.columns()), shortest(dy, grid.rows())),
+            ]
+        })
+        .collect();
+    candidates.sort_by_key(|(dx, dy)| dx.abs() + dy.abs());
+    candidates.dedup();
+
+    candidates.into_iter().find(|(dx, dy)| {
+        live_cells.iter().map(Position::from).all(|cell| {
+            grid.topology()
+                .resolve(cell.row + dy, cell.column + dx, &grid.size())
+                .is_some_and(|moved| later.is_live(&moved))
+        })
+    })
+}
+
+/// The same offset along an axis with the given number of cells, going the
+/// other way around if it is shorter.
+fn shortest(offset: i64, cells: usize) -> i64 {
+    let cells = i64::try_from(cells).expect("the grid size should fit in an i64");
+    let offset = offset.rem_euclid(cells);
+    if offset > cells / 2 {
+        offset - cells
+    } else {
+        offset
+    }
+}
+
+fn live_cells(grid: &Grid) -> Vec<Coordinates> {
+    live_cells_iter(grid).collect()
+}
+
+fn live_cells_iter(grid: &Grid) -> impl Iterator<Item = Coordinates> + '_ {
+    grid.iter().filter(|cell| grid.is_live(cell))
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::domain::{
+        analysis::{analyze, PatternKind, Periodicity},
+        cell::coordinates::Coordinates,
+        fixtures::glider,
+        grid::{
+            functions::{next_generation::next_generation, overlap::overlap},
+            topology::Topology,
+            Grid,
+        },
+        rule::Rule,
+        stability::MAX_PERIOD,
+    };
+
+    fn on_grid(pattern: &Grid, rows: usize, columns: usize, position: Coordinates) -> Grid {
+        overlap(&Grid::of_dead_cells(rows, columns), pattern, &position)
+    }
+
+    fn analyze_with_default_rule(grid: &Grid) -> Option<Periodicity> {
+        analyze(grid, &Rule::default(), MAX_PERIOD)
+    }
+
+    #[test]
+    fn the_glider_should_be_a_diagonal_spaceship_with_period_4() {
+        let grid = on_grid(&glider(), 10, 10, Coordinates::new(3, 3));
+
+        let periodicity = analyze_with_default_rule(&grid).unwrap();
+
+        assert_eq!(
+            periodicity,
+            Periodicity {
+                period: 4,
+                dx: 1,
+                dy: 1
+            }
+        );
+        assert_eq!(periodicity.kind(), PatternKind::Spaceship);
+        assert_eq!(periodicity.speed(), Some("c/4 diagonal".to_owned()));
+    }
+
+    #[test]
+    fn the_glider_should_be_detected_while_crossing_the_torus_edges() {
+        // Four generations later, the glider is split by the bottom and right edges
+        let mut grid =
+            on_grid(&glider(), 6, 6, Coordinates::new(3, 3)).with_topology(Topology::Torus);
+        for _ in 0..4 {
+            grid = next_generation(&grid, &Rule::default());
+        }
+        assert!(grid.is_live(&Coordinates::new(0, 0)));
+
+        assert_eq!(
+            analyze_with_default_rule(&grid),
+            Some(Periodicity {
+                period: 4,
+                dx: 1,
+                dy: 1
+            })
+        );
+    }
+
+    #[test]
+    fn the_lightweight_spaceship_should_move_orthogonally_at_half_the_speed_of_light() {
+        let lightweight_spaceship = Grid::from_str(
+            "⬛⬜⬛⬛⬜
+             ⬜⬛⬛⬛⬛
+             ⬜⬛⬛⬛⬜
+             ⬜⬜⬜⬜⬛",
+        )
+        .unwrap();
+        let grid = on_grid(&lightweight_spaceship, 10, 12, Coordinates::new(3, 5));
+
+        let periodicity = analyze_with_default_rule(&grid).unwrap();
+
+        assert_eq!((periodicity.period, periodicity.dy), (4, 0));
+        assert_eq!(periodicity.dx.abs(), 2);
+        assert_eq!(periodicity.speed(), Some("c/2 orthogonal".to_owned()));
+    }
+
+    #[test]
+    fn still_lifes_and_oscillators_should_not_move() {
+        let block = on_grid(
+            &Grid::from_str("⬜⬜\n⬜⬜").unwrap(),
+            4,
+            4,
+            Coordinates::new(1, 1),
+        );
+        let blinker = on_grid(
+            &Grid::from_str("⬜⬜⬜").unwrap(),
+            5,
+            5,
+            Coordinates::new(2, 1),
+        );
+
+        let block = analyze_with_default_rule(&block).unwrap();
+        let blinker = analyze_with_default_rule(&blinker).unwrap();
+
+        assert_eq!(block.kind(), PatternKind::StillLife);
+        assert_eq!(block.to_string(), "still life");
+        assert_eq!(blinker.kind(), PatternKind::Oscillator);
+        assert_eq!(blinker.to_string(), "oscillator with period 2");
+        assert_eq!(blinker.speed(), None);
+    }
+
+    #[test]
+    fn patterns_that_die_out_are_not_periodic() {
+        let grid = on_grid(
+            &Grid::from_str("⬜⬜").unwrap(),
+            4,
+            4,
+            Coordinates::new(1, 1),
+        );
+
+        assert_eq!(analyze_with_default_rule(&grid), None);
+    }
+
+    #[test]
+    fn the_speed_should_be_reduced() {
+        let periodicity = Periodicity {
+            period: 6,
+            dx: 2,
+            dy: 1,
+        };
+
+        assert_eq!(periodicity.speed(), Some("c/3 oblique".to_owned()));
+        assert_eq!(
+            periodicity.to_string(),
+            "spaceship with period 6, displacement (2, 1) and speed c/3 oblique"
+        );
+    }
+}
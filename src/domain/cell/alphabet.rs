@@ -0,0 +1,159 @@
+use std::{error::Error, fmt, str::FromStr};
+
+use super::state::{ParseCellStateFromCharError, State, DEAD, LIVE};
+
+/// Chars used to read and write live and dead cells.
+///
+/// The default alphabet is the emoji one (`⬜` for live cells and `⬛` for
+/// dead cells), but plain ASCII alphabets like `#`/`.` or `O`/`.` are
+/// easier to type and diff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Alphabet {
+    live: char,
+    dead: char,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseAlphabetError {
+    pub invalid_alphabet: String,
+}
+
+impl Error for ParseAlphabetError {}
+
+impl fmt::Display for ParseAlphabetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Invalid alphabet '{}', it should be two different chars",
+            self.invalid_alphabet
+        )
+    }
+}
+
+impl Default for Alphabet {
+    fn default() -> Self {
+        Self::emoji()
+    }
+}
+
+impl FromStr for Alphabet {
+    type Err = ParseAlphabetError;
+
+    /// The live cell char followed by the dead cell char, for example `#.`.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let chars: Vec<char> = text.chars().collect();
+
+        match chars[..] {
+            [live, dead] if live != dead => Ok(Self::new(live, dead)),
+            _ => Err(ParseAlphabetError {
+                invalid_alphabet: text.to_owned(),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for Alphabet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.live, self.dead)
+    }
+}
+
+impl Alphabet {
+    /// # Panics
+    ///
+    /// Will panic if both chars are the same.
+    #[must_use]
+    pub const fn new(live: char, dead: char) -> Self {
+        assert!(live != dead, "Live and dead cells need different chars");
+        Self { live, dead }
+    }
+
+    #[must_use]
+    pub fn emoji() -> Self {
+        Self::new(LIVE, DEAD)
+    }
+
+    #[must_use]
+    pub fn live(&self) -> char {
+        self.live
+    }
+
+    #[must_use]
+    pub fn dead(&self) -> char {
+        self.dead
+    }
+
+    #[must_use]
+    pub fn char_for(&self, state: &State) -> char {
+        match state {
+            State::Live => self.live,
+            State::Dead => self.dead,
+        }
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the char is not one of the alphabet chars.
+    pub fn state_for(&self, c: char) -> Result<State, ParseCellStateFromCharError> {
+        if c == self.live {
+            Ok(State::Live)
+        } else if c == self.dead {
+            Ok(State::Dead)
+        } else {
+            Err(ParseCellStateFromCharError { invalid_char: c })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::cell::{
+        alphabet::{Alphabet, ParseAlphabetError},
+        state::{State, DEAD, LIVE},
+    };
+
+    #[test]
+    fn the_default_alphabet_uses_emojis() {
+        assert_eq!(Alphabet::default().live(), LIVE);
+        assert_eq!(Alphabet::default().dead(), DEAD);
+    }
+
+    #[test]
+    fn it_should_convert_between_chars_and_states() {
+        let alphabet = Alphabet::new('#', '.');
+
+        assert_eq!(alphabet.state_for('#'), Ok(State::Live));
+        assert_eq!(alphabet.state_for('.'), Ok(State::Dead));
+        assert_eq!(alphabet.char_for(&State::Live), '#');
+        assert_eq!(alphabet.char_for(&State::Dead), '.');
+    }
+
+    #[test]
+    fn it_should_fail_converting_a_char_which_is_not_in_the_alphabet() {
+        assert_eq!(
+            Alphabet::new('O', '.')
+                .state_for('⬜')
+                .unwrap_err()
+                .invalid_char,
+            '⬜'
+        );
+    }
+
+    #[test]
+    fn it_should_be_parsed_from_the_live_and_the_dead_chars() {
+        assert_eq!("O.".parse(), Ok(Alphabet::new('O', '.')));
+        assert_eq!(Alphabet::new('O', '.').to_string(), "O.");
+    }
+
+    #[test]
+    fn it_should_fail_parsing_an_alphabet_without_two_different_chars() {
+        for invalid_alphabet in ["", "O", "OO", "O.#"] {
+            assert_eq!(
+                invalid_alphabet.parse::<Alphabet>(),
+                Err(ParseAlphabetError {
+                    invalid_alphabet: invalid_alphabet.to_owned()
+                })
+            );
+        }
+    }
+}
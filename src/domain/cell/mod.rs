@@ -1,3 +1,4 @@
+pub mod alphabet;
 pub mod coordinates;
 pub mod row;
 pub mod state;
@@ -10,6 +11,8 @@ use self::state::State;
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct Cell {
     state: State,
+    /// Generations the cell has been alive, when it is tracked.
+    age: Option<u32>,
 }
 
 /// # Panics
@@ -41,17 +44,39 @@ impl fmt::Display for Cell {
 
 impl Cell {
     #[must_use]
-    pub fn live() -> Self {
-        Self::new(State::Live)
+    pub const fn live() -> Self {
+        Self {
+            state: State::Live,
+            age: None,
+        }
+    }
+
+    #[must_use]
+    pub const fn dead() -> Self {
+        Self {
+            state: State::Dead,
+            age: None,
+        }
+    }
+
+    pub(crate) fn new(state: State) -> Self {
+        Self { state, age: None }
     }
 
+    /// It returns the same cell with the given age. Dead cells have no age.
     #[must_use]
-    pub fn dead() -> Self {
-        Self::new(State::Dead)
+    pub fn with_age(mut self, age: u32) -> Self {
+        if self.is_live() {
+            self.age = Some(age);
+        }
+        self
     }
 
-    fn new(state: State) -> Self {
-        Self { state }
+    /// Generations the cell has been alive, counting the one it was born
+    /// in, or `None` if its age is not tracked or the cell is dead.
+    #[must_use]
+    pub fn age(&self) -> Option<u32> {
+        self.age
     }
 
     #[must_use]
@@ -99,6 +124,13 @@ mod tests {
         assert!(Cell::new(State::Dead).is_dead());
     }
 
+    #[test]
+    fn a_live_cell_could_track_its_age() {
+        assert_eq!(Cell::live().age(), None);
+        assert_eq!(Cell::live().with_age(3).age(), Some(3));
+        assert_eq!(Cell::dead().with_age(3).age(), None);
+    }
+
     #[test]
     fn a_cell_could_be_displayed_as_a_single_char_string() {
         assert_eq!(format!("{}", Cell::live()), LIVE.to_string());
@@ -3,7 +3,7 @@ use std::{fmt, str::FromStr};
 
 use std::fmt::Write;
 
-use super::state::ParseCellStateFromCharError;
+use super::{alphabet::Alphabet, state::ParseCellStateFromCharError};
 
 #[derive(PartialEq, Debug, Clone)]
 pub struct Row {
@@ -14,16 +14,7 @@ impl FromStr for Row {
     type Err = ParseCellStateFromCharError;
 
     fn from_str(text: &str) -> Result<Self, Self::Err> {
-        let mut cells_row = vec![];
-
-        for c in text.trim().chars() {
-            match Cell::try_from(c) {
-                Ok(cell) => cells_row.push(cell),
-                Err(error) => return Err(error),
-            };
-        }
-
-        Ok(Row::new(cells_row))
+        Self::parse_with(text, &Alphabet::default())
     }
 }
 
@@ -45,6 +36,27 @@ impl Row {
         Self { cells }
     }
 
+    /// It parses a row written with the given alphabet.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if a char is not one of the alphabet chars.
+    pub fn parse_with(
+        text: &str,
+        alphabet: &Alphabet,
+    ) -> Result<Self, ParseCellStateFromCharError> {
+        let mut cells_row = vec![];
+
+        for c in text.trim().chars() {
+            match alphabet.state_for(c) {
+                Ok(state) => cells_row.push(Cell::new(state)),
+                Err(error) => return Err(error),
+            };
+        }
+
+        Ok(Row::new(cells_row))
+    }
+
     #[must_use]
     pub fn with(cells: Vec<Cell>) -> Self {
         Self::new(cells)
@@ -88,7 +100,7 @@ impl Row {
 #[cfg(test)]
 mod tests {
 
-    use crate::domain::cell::{c, Cell};
+    use crate::domain::cell::{alphabet::Alphabet, c, Cell};
 
     use super::Row;
 
@@ -142,6 +154,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn it_should_be_generated_from_a_string_with_another_alphabet() {
+        assert_eq!(
+            Row::parse_with("#.", &Alphabet::new('#', '.')).unwrap(),
+            Row::new(vec![c('⬜'), c('⬛')])
+        );
+    }
+
     #[test]
     fn it_should_fail_trying_to_generate_it_from_an_invalid_string() {
         assert!("X".parse::<Row>().is_err());
@@ -0,0 +1,10 @@
+use std::str::FromStr;
+
+use super::grid::Grid;
+
+/// The smallest spaceship, moving one cell down and one cell right every
+/// four generations.
+pub fn glider() -> Grid {
+    Grid::from_str(include_str!("../../patterns/glider.txt"))
+        .expect("the glider pattern file should be valid")
+}
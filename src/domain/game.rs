@@ -1,41 +1,710 @@
+use std::{
+    error::Error,
+    fmt,
+    hash::Hash,
+    time::{Duration, Instant},
+};
+
 use super::{
     cell::coordinates::Coordinates,
     grid::{
-        functions::{next_generation::next_generation, overlap::overlap},
+        ages::CellAges,
+        functions::{next_generation::next_generation_in_parallel, overlap::overlap},
+        placement::PatternDoesNotFitError,
         Grid,
     },
-    output::printer::Printer,
+    input::keyboard::{Control, Keyboard},
+    outcome::SimulationOutcome,
+    output::{age_colours::render_by_age, printer::Printer, recorder::Recorder},
+    rule::UnboundedB0RuleError,
     settings::Settings,
+    stability::{StabilityDetector, StopReason},
+    statistics::GenerationStats,
     timer::Timer,
+    universe::{position::Position, Universe},
 };
 
-#[must_use]
+#[derive(Debug, PartialEq, Eq)]
+pub enum PlayError {
+    /// The pattern does not fit inside the background grid.
+    PatternDoesNotFit(PatternDoesNotFitError),
+    /// The rule can not be played on an unbounded universe.
+    UnboundedB0Rule(UnboundedB0RuleError),
+}
+
+impl Error for PlayError {}
+
+impl fmt::Display for PlayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PlayError::PatternDoesNotFit(error) => write!(f, "{error}"),
+            PlayError::UnboundedB0Rule(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl From<PatternDoesNotFitError> for PlayError {
+    fn from(error: PatternDoesNotFitError) -> Self {
+        PlayError::PatternDoesNotFit(error)
+    }
+}
+
+impl From<UnboundedB0RuleError> for PlayError {
+    fn from(error: UnboundedB0RuleError) -> Self {
+        PlayError::UnboundedB0Rule(error)
+    }
+}
+
+/// How often the keyboard is read while waiting for the next generation.
+const KEYBOARD_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Generation lifetime after slowing down a game with no delay.
+const MIN_GENERATION_LIFETIME: Duration = Duration::from_millis(10);
+
+/// Where the game is shown and how it waits between generations.
+struct Devices<'a, P: Printer, T: Timer> {
+    console: &'a P,
+    timer: &'a T,
+    /// Only interactive games read the keyboard.
+    keyboard: Option<&'a dyn Keyboard>,
+    recorder: Option<&'a dyn Recorder>,
+}
+
+/// Devices for a game with no terminal. Nothing is shown and the next
+/// generation is calculated right away.
+struct Headless;
+
+impl Printer for Headless {
+    fn clear(&self) {}
+
+    fn print(&self, _output: &str) {}
+}
+
+impl Timer for Headless {
+    fn wait(&self, _duration: Duration) {}
+}
+
+/// # Errors
+///
+/// Will return `Err` if the pattern does not fit inside the background
+/// grid at the position given by the settings placement. On an unbounded
+/// universe the pattern always fits, but rules where dead cells with no
+/// live neighbours are born (`B0`) can not be played.
 pub fn play<P: Printer, T: Timer>(
     settings: &Settings,
     pattern: &Grid,
     console: &P,
     timer: &T,
-) -> String {
+) -> Result<SimulationOutcome, PlayError> {
+    play_on(
+        settings,
+        pattern,
+        &Devices {
+            console,
+            timer,
+            keyboard: None,
+            recorder: None,
+        },
+    )
+}
+
+/// Like `play`, but the player can pause, single-step, change the speed,
+/// restart or quit the game with the keyboard.
+///
+/// # Errors
+///
+/// Will return `Err` like `play`.
+pub fn play_interactively<P: Printer, T: Timer, K: Keyboard>(
+    settings: &Settings,
+    pattern: &Grid,
+    console: &P,
+    timer: &T,
+    keyboard: &K,
+) -> Result<SimulationOutcome, PlayError> {
+    play_on(
+        settings,
+        pattern,
+        &Devices {
+            console,
+            timer,
+            keyboard: Some(keyboard),
+            recorder: None,
+        },
+    )
+}
+
+/// Like `play`, but with no terminal and no wait between generations. Every
+/// generation is given to the recorder.
+///
+/// # Errors
+///
+/// Will return `Err` like `play`.
+pub fn record<R: Recorder>(
+    settings: &Settings,
+    pattern: &Grid,
+    recorder: &R,
+) -> Result<SimulationOutcome, PlayError> {
+    play_on(
+        settings,
+        pattern,
+        &Devices {
+            console: &Headless,
+            timer: &Headless,
+            keyboard: None,
+            recorder: Some(recorder),
+        },
+    )
+}
+
+fn play_on<P: Printer, T: Timer>(
+    settings: &Settings,
+    pattern: &Grid,
+    devices: &Devices<P, T>,
+) -> Result<SimulationOutcome, PlayError> {
+    if settings.unbounded {
+        settings.rule.check_unbounded()?;
+
+        let pattern_position = settings
+            .placement
+            .origin(&settings.back_grid_size, &pattern.size());
+        return Ok(play_on_unbounded_universe(
+            settings,
+            pattern,
+            &pattern_position,
+            devices,
+        ));
+    }
+
+    let pattern_position = settings
+        .placement
+        .position(&settings.back_grid_size, &pattern.size())?;
+
     let back_grid = Grid::of_dead_cells(
         settings.back_grid_size.rows,
         settings.back_grid_size.columns,
-    );
+    )
+    .with_topology(settings.topology);
+
+    let grid = overlap(&back_grid, pattern, &pattern_position);
+    // The ages are carried along the generations, only when they are shown
+    let grid = if paints_by_age(settings) {
+        let ages = CellAges::new(&grid);
+        grid.with_ages(ages)
+    } else {
+        grid
+    };
+
+    Ok(play_generations(
+        settings,
+        &grid,
+        Grid::clone,
+        Grid::population,
+        |grid| next_generation_in_parallel(grid, &settings.rule, settings.threads),
+        devices,
+    ))
+}
+
+/// The background grid is only a viewport into the universe. The pattern is
+/// placed at the universe origin and the viewport is moved so that the
+/// pattern is shown at the given position.
+fn play_on_unbounded_universe<P: Printer, T: Timer>(
+    settings: &Settings,
+    pattern: &Grid,
+    pattern_position: &Coordinates,
+    devices: &Devices<P, T>,
+) -> SimulationOutcome {
+    let pattern_position = Position::from(pattern_position);
+    let viewport_origin = Position::new(-pattern_position.row, -pattern_position.column);
+    let universe = if paints_by_age(settings) {
+        Universe::from(pattern).with_ages()
+    } else {
+        Universe::from(pattern)
+    };
+
+    play_generations(
+        settings,
+        &universe,
+        |universe| universe.viewport(&viewport_origin, &settings.back_grid_size),
+        Universe::population,
+        |universe| universe.next_generation(&settings.rule),
+        devices,
+    )
+}
+
+/// Number of the next generation, which is also the generations played so
+/// far. They are never more than the `u32` generation limit, but the number
+/// saturates rather than wrapping if they were.
+fn generation_number(population_history: &[usize]) -> u32 {
+    u32::try_from(population_history.len()).unwrap_or(u32::MAX)
+}
+
+/// What to do after showing a generation.
+enum Next {
+    Generation,
+    Restart,
+    Quit,
+}
+
+/// It shows every generation until the generation limit is reached or,
+/// in the until stable mode, the game becomes stable. Interactive games
+/// can also be restarted or stopped by the player.
+fn play_generations<S, P, T>(
+    settings: &Settings,
+    initial_state: &S,
+    view: impl Fn(&S) -> Grid,
+    population: impl Fn(&S) -> usize,
+    next_generation: impl Fn(&S) -> S,
+    devices: &Devices<P, T>,
+) -> SimulationOutcome
+where
+    S: Hash + Clone,
+    P: Printer,
+    T: Timer,
+{
+    let start = devices.timer.now();
+    let mut state = initial_state.clone();
+    let mut detector = StabilityDetector::new();
+    let mut final_grid = Grid::default();
+    let mut population_history = Vec::new();
+    let mut statistics: Vec<GenerationStats> = Vec::new();
+    let mut playback = Playback::new(settings.generation_lifetime);
+
+    let outcome =
+        |final_grid, population_history: Vec<usize>, statistics, stop_reason| SimulationOutcome {
+            final_grid,
+            generations: generation_number(&population_history),
+            population_history,
+            statistics,
+            stop_reason,
+            elapsed: devices.timer.now() - start,
+        };
+
+    while population_history.len() < settings.generations as usize {
+        let frame_start = devices.timer.now();
+
+        let grid = view(&state);
+        // Only games that keep or show the statistics pay for them
+        let generation_stats = (settings.statistics || settings.status_line).then(|| {
+            GenerationStats::of(
+                generation_number(&population_history),
+                &grid,
+                (!population_history.is_empty()).then_some(&final_grid),
+            )
+        });
+        final_grid = grid;
+        population_history.push(population(&state));
 
-    // todo: put the pattern in the center of the background grid
-    let mut grid = overlap(&back_grid, pattern, &Coordinates::new(13, 29));
+        if let Some(recorder) = devices.recorder {
+            recorder.record(&final_grid);
+        }
 
-    let mut output = String::new();
+        let mut frame = render_frame(settings, &final_grid);
+        if let Some(stats) = generation_stats.as_ref().filter(|_| settings.status_line) {
+            frame.push_str(&stats.status_line());
+            frame.push('\n');
+        }
+        devices.console.print_frame(&frame);
+        if let Some(stats) = generation_stats.filter(|_| settings.statistics) {
+            statistics.push(stats);
+        }
 
-    for _iter in 0..settings.generations {
-        output = grid.to_string();
+        if settings.until_stable {
+            if let Some(stop_reason) = detector.observe(&state, population(&state)) {
+                return outcome(final_grid, population_history, statistics, stop_reason);
+            }
+        }
 
-        console.clear();
-        console.print(&output);
+        let next_state = next_generation(&state);
 
-        timer.wait(settings.generation_lifetime);
+        let next = if let Some(keyboard) = devices.keyboard {
+            playback.wait_for_next(keyboard, devices.timer, frame_start)
+        } else {
+            devices
+                .timer
+                .wait_until_frame_end(frame_start, playback.generation_lifetime);
+            Next::Generation
+        };
 
-        grid = next_generation(&grid);
+        match next {
+            Next::Generation => state = next_state,
+            Next::Restart => {
+                state = initial_state.clone();
+                detector = StabilityDetector::new();
+                population_history.clear();
+                statistics.clear();
+            }
+            Next::Quit => {
+                return outcome(final_grid, population_history, statistics, StopReason::Quit)
+            }
+        }
     }
 
-    output
+    outcome(
+        final_grid,
+        population_history,
+        statistics,
+        StopReason::GenerationLimit,
+    )
+}
+
+/// Cells are painted by age in the render modes drawing one cell per char.
+fn paints_by_age(settings: &Settings) -> bool {
+    (settings.colour_by_age || settings.trail)
+        && settings
+            .render_mode
+            .cell_width(&settings.alphabet)
+            .is_some()
+}
+
+/// It renders the generation with the render mode or, when the cells are
+/// coloured by age, painting them with the ages the grid tracks.
+fn render_frame(settings: &Settings, grid: &Grid) -> String {
+    let cell_width = settings.render_mode.cell_width(&settings.alphabet);
+
+    match (cell_width, grid.ages()) {
+        (Some(cell_width), Some(ages)) => render_by_age(ages, cell_width, settings.trail),
+        _ => settings.render_mode.render(grid, &settings.alphabet),
+    }
+}
+
+/// Speed and pause state of an interactive game.
+struct Playback {
+    paused: bool,
+    generation_lifetime: Duration,
+}
+
+impl Playback {
+    fn new(generation_lifetime: Duration) -> Self {
+        Self {
+            paused: false,
+            generation_lifetime,
+        }
+    }
+
+    /// It reads the keyboard until it is time to show the next generation,
+    /// forever while the game is paused, unless the player steps, restarts
+    /// or quits the game.
+    fn wait_for_next<T: Timer>(
+        &mut self,
+        keyboard: &dyn Keyboard,
+        timer: &T,
+        frame_start: Instant,
+    ) -> Next {
+        loop {
+            while let Some(control) = keyboard.read_control() {
+                match control {
+                    Control::PauseOrResume => self.paused = !self.paused,
+                    Control::Step if self.paused => return Next::Generation,
+                    Control::Step => {}
+                    Control::SpeedUp => self.generation_lifetime /= 2,
+                    Control::SlowDown => {
+                        self.generation_lifetime =
+                            (self.generation_lifetime * 2).max(MIN_GENERATION_LIFETIME);
+                    }
+                    Control::Restart => return Next::Restart,
+                    Control::Quit => return Next::Quit,
+                }
+            }
+
+            let left = self
+                .generation_lifetime
+                .saturating_sub(timer.now().saturating_duration_since(frame_start));
+
+            if !self.paused && left.is_zero() {
+                return Next::Generation;
+            }
+
+            timer.wait(if self.paused {
+                KEYBOARD_POLL_INTERVAL
+            } else {
+                left.min(KEYBOARD_POLL_INTERVAL)
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::{Cell, RefCell},
+        collections::VecDeque,
+        str::FromStr,
+        time::{Duration, Instant},
+    };
+
+    use crate::domain::{
+        cell::alphabet::Alphabet,
+        game::{play, play_interactively, record, PlayError},
+        grid::{placement::Placement, size::Size, topology::Topology, Grid},
+        input::keyboard::{Key, Keyboard},
+        outcome::SimulationOutcome,
+        output::{logger::Logger, recorder::Recorder, render_mode::RenderMode},
+        rule::{Rule, UnboundedB0RuleError},
+        settings::Settings,
+        stability::StopReason,
+        timer::Timer,
+    };
+
+    struct NoWait;
+
+    impl Timer for NoWait {
+        fn wait(&self, _duration: Duration) {}
+    }
+
+    /// A clock that only moves forward when waiting.
+    struct FakeClock {
+        now: Cell<Instant>,
+    }
+
+    impl FakeClock {
+        fn new() -> Self {
+            Self {
+                now: Cell::new(Instant::now()),
+            }
+        }
+    }
+
+    impl Timer for FakeClock {
+        fn wait(&self, duration: Duration) {
+            self.now.set(self.now.get() + duration);
+        }
+
+        fn now(&self) -> Instant {
+            self.now.get()
+        }
+    }
+
+    /// Every time the keyboard is read, it gives the next key in the list,
+    /// or none. A game with no delay reads the keyboard until there is no
+    /// key, so `None` separates the keys for every generation.
+    struct FakeKeyboard {
+        keys: RefCell<VecDeque<Option<Key>>>,
+    }
+
+    impl FakeKeyboard {
+        fn pressing(keys: &[Option<char>]) -> Self {
+            Self {
+                keys: RefCell::new(keys.iter().map(|key| key.map(Key::Char)).collect()),
+            }
+        }
+    }
+
+    impl Keyboard for FakeKeyboard {
+        fn read_key(&self) -> Option<Key> {
+            self.keys.borrow_mut().pop_front().flatten()
+        }
+
+        fn wait_for_key(&self) -> Option<Key> {
+            self.read_key()
+        }
+    }
+
+    #[derive(Default)]
+    struct FakeRecorder {
+        generations: RefCell<Vec<Grid>>,
+    }
+
+    impl Recorder for FakeRecorder {
+        fn record(&self, generation: &Grid) {
+            self.generations.borrow_mut().push(generation.clone());
+        }
+    }
+
+    fn settings(until_stable: bool, unbounded: bool) -> Settings {
+        Settings {
+            back_grid_size: Size::new(5, 5),
+            generations: 10,
+            generation_lifetime: Duration::ZERO,
+            rule: Rule::default(),
+            topology: Topology::default(),
+            unbounded,
+            threads: 1,
+            alphabet: Alphabet::default(),
+            placement: Placement::default(),
+            until_stable,
+            render_mode: RenderMode::default(),
+            colour_by_age: false,
+            trail: false,
+            status_line: false,
+            statistics: false,
+        }
+    }
+
+    fn blinker() -> Grid {
+        Grid::from_str("⬜⬜⬜").unwrap()
+    }
+
+    #[test]
+    fn it_should_play_all_the_generations_by_default() {
+        let outcome = play(&settings(false, false), &blinker(), &Logger::new(), &NoWait).unwrap();
+
+        assert_eq!(outcome.generations, 10);
+        assert_eq!(outcome.population_history, vec![3; 10]);
+        assert_eq!(outcome.stop_reason, StopReason::GenerationLimit);
+    }
+
+    #[test]
+    fn it_should_stop_when_the_pattern_oscillates() {
+        for unbounded in [false, true] {
+            let outcome = play(
+                &settings(true, unbounded),
+                &blinker(),
+                &Logger::new(),
+                &NoWait,
+            )
+            .unwrap();
+
+            assert_eq!(outcome.generations, 3);
+            assert_eq!(outcome.final_grid.population(), 3);
+            assert_eq!(outcome.stop_reason, StopReason::Oscillation { period: 2 });
+        }
+    }
+
+    #[test]
+    fn rules_with_births_from_no_live_neighbours_can_not_be_played_unbounded() {
+        let mut settings = settings(false, true);
+        settings.rule = "B0/S8".parse().unwrap();
+
+        assert_eq!(
+            play(&settings, &blinker(), &Logger::new(), &NoWait),
+            Err(PlayError::UnboundedB0Rule(UnboundedB0RuleError {
+                rule: settings.rule.clone()
+            }))
+        );
+
+        settings.unbounded = false;
+        assert!(play(&settings, &blinker(), &Logger::new(), &NoWait).is_ok());
+    }
+
+    #[test]
+    fn it_should_stop_when_the_pattern_dies_out() {
+        let pattern = Grid::from_str("⬜⬜").unwrap();
+
+        let outcome = play(&settings(true, false), &pattern, &Logger::new(), &NoWait).unwrap();
+
+        assert_eq!(outcome.population_history, vec![2, 0]);
+        assert_eq!(outcome.stop_reason, StopReason::Extinction);
+    }
+
+    #[test]
+    fn the_cells_could_be_coloured_by_age() {
+        let mut settings = settings(false, false);
+        settings.generations = 2;
+        settings.colour_by_age = true;
+        let console = Logger::new();
+
+        play(&settings, &blinker(), &console, &NoWait).unwrap();
+
+        // The centre of the blinker is born in the first generation and
+        // still alive in the second one
+        assert!(console.log().contains("\x1B[48;5;231m"));
+        assert!(console.log().contains("\x1B[48;5;226m"));
+    }
+
+    #[test]
+    fn the_cells_could_be_coloured_by_age_on_an_unbounded_universe() {
+        let mut settings = settings(false, true);
+        settings.generations = 2;
+        settings.colour_by_age = true;
+        let console = Logger::new();
+
+        play(&settings, &blinker(), &console, &NoWait).unwrap();
+
+        assert!(console.log().contains("\x1B[48;5;231m"));
+        assert!(console.log().contains("\x1B[48;5;226m"));
+    }
+
+    #[test]
+    fn every_generation_should_be_recorded() {
+        let recorder = FakeRecorder::default();
+
+        let outcome = record(&settings(true, false), &blinker(), &recorder).unwrap();
+
+        let generations = recorder.generations.into_inner();
+        assert_eq!(generations.len(), 3);
+        assert_eq!(generations[0], generations[2]);
+        assert_ne!(generations[0], generations[1]);
+        assert_eq!(generations[2], outcome.final_grid);
+    }
+
+    #[test]
+    fn the_statistics_of_every_generation_should_be_collected() {
+        let mut settings = settings(true, false);
+        settings.statistics = true;
+
+        let outcome = play(&settings, &blinker(), &Logger::new(), &NoWait).unwrap();
+
+        let births: Vec<usize> = outcome
+            .statistics
+            .iter()
+            .map(|stats| stats.births)
+            .collect();
+        assert_eq!(births, vec![0, 2, 2]);
+    }
+
+    #[test]
+    fn the_statistics_should_only_be_collected_when_asked_for() {
+        let outcome = play(&settings(true, false), &blinker(), &Logger::new(), &NoWait).unwrap();
+
+        assert!(outcome.statistics.is_empty());
+    }
+
+    #[test]
+    fn the_statistics_could_be_shown_under_the_grid() {
+        let mut settings = settings(false, false);
+        settings.generations = 1;
+        settings.status_line = true;
+        let console = Logger::new();
+
+        play(&settings, &blinker(), &console, &NoWait).unwrap();
+
+        assert!(console
+            .log()
+            .contains("Generation 0  live 3  births 0  deaths 0"));
+    }
+
+    fn play_pressing(generation_lifetime: Duration, keys: &[Option<char>]) -> SimulationOutcome {
+        let mut settings = settings(false, false);
+        settings.generation_lifetime = generation_lifetime;
+
+        play_interactively(
+            &settings,
+            &Grid::from_str("⬜⬜").unwrap(),
+            &Logger::new(),
+            &FakeClock::new(),
+            &FakeKeyboard::pressing(keys),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn the_player_should_be_able_to_quit_the_game() {
+        let outcome = play_pressing(Duration::ZERO, &[None, Some('q')]);
+
+        assert_eq!(outcome.generations, 2);
+        assert_eq!(outcome.stop_reason, StopReason::Quit);
+    }
+
+    #[test]
+    fn the_player_should_be_able_to_restart_the_game() {
+        let outcome = play_pressing(Duration::ZERO, &[None, None, Some('r'), Some('q')]);
+
+        assert_eq!(outcome.population_history, vec![2]);
+    }
+
+    #[test]
+    fn the_player_should_be_able_to_step_while_the_game_is_paused() {
+        let outcome = play_pressing(Duration::from_secs(1), &[Some(' '), Some('n'), Some('q')]);
+
+        assert_eq!(outcome.population_history, vec![2, 0]);
+        assert_eq!(outcome.elapsed, Duration::ZERO);
+    }
+
+    #[test]
+    fn the_player_should_be_able_to_change_the_speed() {
+        let outcome = play_pressing(Duration::from_secs(1), &[Some('+'), Some('+'), Some('-')]);
+
+        assert_eq!(outcome.generations, 10);
+        assert_eq!(outcome.elapsed, Duration::from_secs(5));
+    }
 }
@@ -0,0 +1,171 @@
+use crate::domain::cell::{coordinates::Coordinates, Cell};
+
+use super::Grid;
+
+/// How long a cell has been alive, or dead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Age {
+    /// Generations the cell has been alive, 1 in the generation it was born.
+    Live(u32),
+    /// Generations since the cell died, 1 in the generation it died.
+    Dead(u32),
+    /// The cell has not been alive since the ages started to be tracked.
+    Unborn,
+}
+
+/// Age of every cell of a grid along the generations. A grid only tracks
+/// them when asked to, so only the games that show them pay for them.
+///
+/// Cells alive when the tracking starts are 1 generation old. A cell only
+/// gets older while it stays alive in the next generation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CellAges {
+    rows: usize,
+    columns: usize,
+    ages: Vec<Age>,
+}
+
+impl CellAges {
+    #[must_use]
+    pub fn new(grid: &Grid) -> Self {
+        Self::of(grid.rows(), grid.columns(), |coordinates| {
+            if grid.is_live(coordinates) {
+                Age::Live(1)
+            } else {
+                Age::Unborn
+            }
+        })
+    }
+
+    /// Ages of a grid with the given size, taken from the given function.
+    #[must_use]
+    pub fn of(rows: usize, columns: usize, age: impl Fn(&Coordinates) -> Age) -> Self {
+        Self {
+            rows,
+            columns,
+            ages: (0..rows)
+                .flat_map(|row| (0..columns).map(move |column| Coordinates::new(row, column)))
+                .map(|coordinates| age(&coordinates))
+                .collect(),
+        }
+    }
+
+    #[must_use]
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    #[must_use]
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// It updates the ages with the next generation. If the grid size
+    /// changed, the tracking starts again.
+    pub fn update(&mut self, next_generation: &Grid) {
+        if self.rows != next_generation.rows() || self.columns != next_generation.columns() {
+            *self = Self::new(next_generation);
+            return;
+        }
+
+        for (age, coordinates) in self.ages.iter_mut().zip(next_generation.iter()) {
+            *age = match (*age, next_generation.is_live(&coordinates)) {
+                (Age::Live(generations), true) => Age::Live(generations.saturating_add(1)),
+                (Age::Dead(_) | Age::Unborn, true) => Age::Live(1),
+                (Age::Live(_), false) => Age::Dead(1),
+                (Age::Dead(generations), false) => Age::Dead(generations.saturating_add(1)),
+                (Age::Unborn, false) => Age::Unborn,
+            };
+        }
+    }
+
+    /// # Panics
+    ///
+    /// Will panic if the coordinates are outside the grid.
+    #[must_use]
+    pub fn age(&self, coordinates: &Coordinates) -> Age {
+        assert!(
+            coordinates.row < self.rows && coordinates.column < self.columns,
+            "Coordinates should be inside the grid"
+        );
+        self.ages[coordinates.row * self.columns + coordinates.column]
+    }
+
+    /// The cell at the given coordinates, with its age if it is alive.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the coordinates are outside the grid.
+    #[must_use]
+    pub fn cell(&self, coordinates: &Coordinates) -> Cell {
+        match self.age(coordinates) {
+            Age::Live(generations) => Cell::live().with_age(generations),
+            Age::Dead(_) | Age::Unborn => Cell::dead(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::domain::{
+        cell::coordinates::Coordinates,
+        grid::{
+            ages::{Age, CellAges},
+            functions::next_generation::next_generation,
+            Grid,
+        },
+        rule::Rule,
+    };
+
+    fn blinker() -> Grid {
+        Grid::from_str(
+            "⬛⬛⬛⬛⬛
+             ⬛⬛⬛⬛⬛
+             ⬛⬜⬜⬜⬛
+             ⬛⬛⬛⬛⬛
+             ⬛⬛⬛⬛⬛",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn cells_should_get_older_while_they_stay_alive() {
+        let mut grid = blinker();
+        let mut ages = CellAges::new(&grid);
+
+        grid = next_generation(&grid, &Rule::default());
+        ages.update(&grid);
+        grid = next_generation(&grid, &Rule::default());
+        ages.update(&grid);
+
+        // The center of the blinker never dies
+        assert_eq!(ages.age(&Coordinates::new(2, 2)), Age::Live(3));
+        assert_eq!(ages.cell(&Coordinates::new(2, 2)).age(), Some(3));
+        // The ends of the blinker die and are born again
+        assert_eq!(ages.age(&Coordinates::new(2, 1)), Age::Live(1));
+        assert_eq!(ages.age(&Coordinates::new(1, 2)), Age::Dead(1));
+        assert_eq!(ages.age(&Coordinates::new(0, 0)), Age::Unborn);
+    }
+
+    #[test]
+    fn dead_cells_should_count_the_generations_since_they_died() {
+        let mut ages = CellAges::new(&blinker());
+
+        ages.update(&Grid::of_dead_cells(5, 5));
+        ages.update(&Grid::of_dead_cells(5, 5));
+
+        assert_eq!(ages.age(&Coordinates::new(2, 1)), Age::Dead(2));
+        assert!(ages.cell(&Coordinates::new(2, 1)).is_dead());
+    }
+
+    #[test]
+    fn the_tracking_should_start_again_when_the_grid_size_changes() {
+        let mut ages = CellAges::new(&blinker());
+
+        ages.update(&Grid::of_live_cells(2, 2));
+
+        assert_eq!(ages.age(&Coordinates::new(0, 0)), Age::Live(1));
+    }
+}
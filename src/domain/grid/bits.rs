@@ -0,0 +1,152 @@
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Bit-packed storage for the grid cells.
+///
+/// Every cell is one bit (1 for a live cell) and every row is stored in
+/// whole 64-bit words. The cell at column `c` is the bit `c % 64` of the
+/// word `c / 64` of its row. Bits after the last column are always zero.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BitRows {
+    rows: usize,
+    columns: usize,
+    words_per_row: usize,
+    words: Vec<u64>,
+}
+
+impl BitRows {
+    /// Storage with all the cells dead.
+    #[must_use]
+    pub fn new(rows: usize, columns: usize) -> Self {
+        let words_per_row = columns.div_ceil(WORD_BITS);
+        Self {
+            rows,
+            columns,
+            words_per_row,
+            words: vec![0; rows * words_per_row],
+        }
+    }
+
+    #[must_use]
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    #[must_use]
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    #[must_use]
+    pub fn words_per_row(&self) -> usize {
+        self.words_per_row
+    }
+
+    #[must_use]
+    pub fn get(&self, row: usize, column: usize) -> bool {
+        self.row(row)[column / WORD_BITS] & (1 << (column % WORD_BITS)) != 0
+    }
+
+    pub fn set(&mut self, row: usize, column: usize, live: bool) {
+        let word = &mut self.row_mut(row)[column / WORD_BITS];
+        if live {
+            *word |= 1 << (column % WORD_BITS);
+        } else {
+            *word &= !(1 << (column % WORD_BITS));
+        }
+    }
+
+    #[must_use]
+    pub fn row(&self, row: usize) -> &[u64] {
+        &self.words[row * self.words_per_row..(row + 1) * self.words_per_row]
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> &mut [u64] {
+        &mut self.words[row * self.words_per_row..(row + 1) * self.words_per_row]
+    }
+
+    /// All the rows one after the other.
+    pub fn words_mut(&mut self) -> &mut [u64] {
+        &mut self.words
+    }
+
+    /// The same row with the columns in reverse order.
+    #[must_use]
+    pub fn reversed_row(&self, row: usize) -> Vec<u64> {
+        let mut reversed = vec![0; self.words_per_row];
+        for column in 0..self.columns {
+            if self.get(row, column) {
+                let reversed_column = self.columns - 1 - column;
+                reversed[reversed_column / WORD_BITS] |= 1 << (reversed_column % WORD_BITS);
+            }
+        }
+        reversed
+    }
+
+    /// Mask with the bits of the last word of a row which are real columns.
+    #[must_use]
+    pub fn last_word_mask(&self) -> u64 {
+        match self.columns % WORD_BITS {
+            0 => u64::MAX,
+            used_bits => (1 << used_bits) - 1,
+        }
+    }
+
+    /// Number of live cells.
+    #[must_use]
+    pub fn count_live(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::grid::bits::BitRows;
+
+    #[test]
+    fn all_the_cells_are_dead_in_a_new_storage() {
+        let bits = BitRows::new(2, 3);
+
+        assert_eq!(bits.count_live(), 0);
+        assert!(!bits.get(1, 2));
+    }
+
+    #[test]
+    fn it_should_set_and_unset_cells() {
+        let mut bits = BitRows::new(2, 100);
+
+        bits.set(1, 99, true);
+        assert!(bits.get(1, 99));
+        assert_eq!(bits.count_live(), 1);
+
+        bits.set(1, 99, false);
+        assert!(!bits.get(1, 99));
+    }
+
+    #[test]
+    fn rows_are_stored_in_whole_words() {
+        let mut bits = BitRows::new(2, 65);
+
+        bits.set(0, 64, true);
+
+        assert_eq!(bits.words_per_row(), 2);
+        assert_eq!(bits.row(0), &[0, 1]);
+    }
+
+    #[test]
+    fn it_should_return_a_row_with_the_columns_in_reverse_order() {
+        let mut bits = BitRows::new(1, 3);
+
+        bits.set(0, 0, true);
+
+        assert_eq!(bits.reversed_row(0), vec![0b100]);
+    }
+
+    #[test]
+    fn it_should_return_the_mask_for_the_used_bits_of_the_last_row_word() {
+        assert_eq!(BitRows::new(1, 3).last_word_mask(), 0b111);
+        assert_eq!(BitRows::new(1, 64).last_word_mask(), u64::MAX);
+    }
+}
@@ -0,0 +1,90 @@
+use crate::domain::cell::coordinates::Coordinates;
+
+use super::Grid;
+
+/// Smallest rectangle with all the live cells of a grid. Rows and columns
+/// are inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoundingBox {
+    pub top: usize,
+    pub left: usize,
+    pub bottom: usize,
+    pub right: usize,
+}
+
+impl BoundingBox {
+    /// Bounding box of the live cells, or none if there are no live cells.
+    #[must_use]
+    pub fn of(grid: &Grid) -> Option<Self> {
+        grid.iter().filter(|cell| grid.is_live(cell)).fold(
+            None,
+            |bounding_box: Option<Self>, cell| {
+                Some(match bounding_box {
+                    None => Self {
+                        top: cell.row,
+                        left: cell.column,
+                        bottom: cell.row,
+                        right: cell.column,
+                    },
+                    Some(bounding_box) => Self {
+                        top: bounding_box.top.min(cell.row),
+                        left: bounding_box.left.min(cell.column),
+                        bottom: bounding_box.bottom.max(cell.row),
+                        right: bounding_box.right.max(cell.column),
+                    },
+                })
+            },
+        )
+    }
+
+    #[must_use]
+    pub fn rows(&self) -> usize {
+        self.bottom - self.top + 1
+    }
+
+    #[must_use]
+    pub fn columns(&self) -> usize {
+        self.right - self.left + 1
+    }
+
+    #[must_use]
+    pub fn top_left(&self) -> Coordinates {
+        Coordinates::new(self.top, self.left)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::domain::grid::{bounding_box::BoundingBox, Grid};
+
+    #[test]
+    fn it_should_contain_all_the_live_cells() {
+        let grid = Grid::from_str(
+            "⬛⬛⬛⬛
+             ⬛⬛⬜⬛
+             ⬛⬜⬛⬛
+             ⬛⬛⬛⬛",
+        )
+        .unwrap();
+
+        let bounding_box = BoundingBox::of(&grid).unwrap();
+
+        assert_eq!(
+            bounding_box,
+            BoundingBox {
+                top: 1,
+                left: 1,
+                bottom: 2,
+                right: 2
+            }
+        );
+        assert_eq!((bounding_box.rows(), bounding_box.columns()), (2, 2));
+    }
+
+    #[test]
+    fn a_grid_without_live_cells_has_no_bounding_box() {
+        assert_eq!(BoundingBox::of(&Grid::of_dead_cells(2, 2)), None);
+    }
+}
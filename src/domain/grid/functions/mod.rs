@@ -1,2 +1,3 @@
 pub mod next_generation;
 pub mod overlap;
+pub mod transform;
@@ -1,46 +1,275 @@
+use std::{mem, thread};
+
 use crate::domain::cell::coordinates::Coordinates;
 use crate::domain::cell::row::Row;
 use crate::domain::cell::state::State;
 use crate::domain::cell::Cell;
+use crate::domain::grid::bits::BitRows;
 use crate::domain::grid::{CellInfo, Grid};
+use crate::domain::rule::Rule;
 
-/// Calculate the next generation of cells for the grid
+const MAX_NEIGHBORS: usize = 8;
 
+/// Calculate the next generation of cells for the grid applying the given rule.
+///
+/// It works with the bit-packed grid storage. Instead of counting the live
+/// neighbours cell by cell, it calculates the counts for 64 cells at once
+/// adding the eight neighbour rows (shifted one column to the left or to the
+/// right) with bitwise operations.
 #[must_use]
-pub fn next_generation(grid: &Grid) -> Grid {
+pub fn next_generation(grid: &Grid, rule: &Rule) -> Grid {
+    if grid.is_empty() {
+        return grid.clone();
+    }
+
+    if grid.number_of_cells() == 1 {
+        return next_generation_cell_by_cell(grid, rule);
+    }
+
+    let rule_masks = RuleMasks::new(rule);
+
+    let mut cells = BitRows::new(grid.rows(), grid.columns());
+
+    calculate_next_rows(grid, 0, &rule_masks, cells.words_mut());
+
+    Grid::with_cells(cells)
+        .with_topology(grid.topology())
+        .with_ages_after(grid)
+}
+
+/// Calculate the next generation of cells like `next_generation` but using
+/// several threads.
+///
+/// The grid is split into bands of consecutive rows and every thread
+/// calculates one band. The result is the same as the sequential version.
+#[must_use]
+pub fn next_generation_in_parallel(grid: &Grid, rule: &Rule, threads: usize) -> Grid {
+    if threads <= 1 || grid.number_of_cells() <= 1 {
+        return next_generation(grid, rule);
+    }
+
+    let rule_masks = RuleMasks::new(rule);
+
+    let mut cells = BitRows::new(grid.rows(), grid.columns());
+    let words_per_row = cells.words_per_row();
+    let rows_per_band = grid.rows().div_ceil(threads);
+
+    thread::scope(|scope| {
+        for (band, band_words) in cells
+            .words_mut()
+            .chunks_mut(rows_per_band * words_per_row)
+            .enumerate()
+        {
+            let rule_masks = &rule_masks;
+            scope.spawn(move || {
+                calculate_next_rows(grid, band * rows_per_band, rule_masks, band_words);
+            });
+        }
+    });
+
+    Grid::with_cells(cells)
+        .with_topology(grid.topology())
+        .with_ages_after(grid)
+}
+
+/// Slow version that counts the live neighbours of every cell one by one.
+/// It is used for grids with a single cell, not worth packing into words,
+/// and the tests check the word-level version against it.
+fn next_generation_cell_by_cell(grid: &Grid, rule: &Rule) -> Grid {
     let mut cell_rows = vec![];
 
     for row in 0..grid.rows() {
         let mut cells_row = vec![];
         for column in 0..grid.columns() {
-            cells_row.push(new_cell_applying_rule_b3_s23(
+            cells_row.push(new_cell_applying_rule(
                 &grid.get_cell_info(&Coordinates::new(row, column)),
+                rule,
             ));
         }
         cell_rows.push(Row::new(cells_row));
     }
 
     Grid::new(cell_rows)
+        .with_topology(grid.topology())
+        .with_ages_after(grid)
 }
 
-/// Game of Life standard.
-///
-/// Rule-string notation: B3/S23
-///
 /// A cell:
-/// - is born if it has exactly three neighbours,
-/// - survives if it has two or three living neighbours,
+/// - is born if its number of live neighbours is in the rule birth list,
+/// - survives if its number of live neighbours is in the rule survival list,
+///
 /// and dies otherwise.
-fn new_cell_applying_rule_b3_s23(cell_info: &CellInfo) -> Cell {
-    match cell_info.state {
-        State::Live => match cell_info.number_of_live_neighbors {
-            2 | 3 => Cell::live(),
-            _ => Cell::dead(),
-        },
-        State::Dead => match cell_info.number_of_live_neighbors {
-            3 => Cell::live(),
-            _ => Cell::dead(),
-        },
+fn new_cell_applying_rule(cell_info: &CellInfo, rule: &Rule) -> Cell {
+    match rule.next_state(&cell_info.state, cell_info.number_of_live_neighbors) {
+        State::Live => Cell::live(),
+        State::Dead => Cell::dead(),
+    }
+}
+
+/// Neighbour counts in the birth and survival lists of the rule.
+struct RuleMasks {
+    birth: Vec<usize>,
+    survival: Vec<usize>,
+}
+
+impl RuleMasks {
+    fn new(rule: &Rule) -> Self {
+        Self {
+            birth: (0..=MAX_NEIGHBORS).filter(|n| rule.is_born(*n)).collect(),
+            survival: (0..=MAX_NEIGHBORS).filter(|n| rule.survives(*n)).collect(),
+        }
+    }
+}
+
+/// Cells of a row as seen from the row below or above it, or from the same row.
+struct NeighborRow {
+    /// The cells in the same column
+    center: Vec<u64>,
+    /// The cells in the column to the left
+    west: Vec<u64>,
+    /// The cells in the column to the right
+    east: Vec<u64>,
+}
+
+impl NeighborRow {
+    /// `row` can be outside the grid. The grid topology decides which row it is.
+    fn new(grid: &Grid, row: i64) -> Self {
+        let size = grid.size();
+        let topology = grid.topology();
+        let last_column = grid.columns() - 1;
+
+        let center = match topology.resolve(row, 0, &size) {
+            None => vec![0; grid.cells().words_per_row()],
+            Some(first_cell) if first_cell.column == 0 => grid.cells().row(first_cell.row).to_vec(),
+            // Edges stitched together with a twist
+            Some(first_cell) => grid.cells().reversed_row(first_cell.row),
+        };
+
+        let is_live = |column: i64| {
+            topology
+                .resolve(row, column, &size)
+                .is_some_and(|coordinates| grid.is_live(&coordinates))
+        };
+        let left_ghost = u64::from(is_live(-1));
+        let right_ghost = u64::from(is_live(
+            i64::try_from(grid.columns()).expect("the columns should fit in an i64"),
+        ));
+
+        let words = center.len();
+        let mut west = vec![0; words];
+        let mut east = vec![0; words];
+
+        for word in 0..words {
+            let previous = if word == 0 {
+                left_ghost
+            } else {
+                center[word - 1] >> 63
+            };
+            west[word] = (center[word] << 1) | previous;
+
+            let next = if word + 1 == words {
+                0
+            } else {
+                center[word + 1] << 63
+            };
+            east[word] = (center[word] >> 1) | next;
+        }
+        east[last_column / 64] |= right_ghost << (last_column % 64);
+
+        Self { center, west, east }
+    }
+}
+
+/// Bit-sliced counter. Bit `i` of the `bits[b]` word is the bit `b` of the
+/// counter for the cell `i` of the word.
+#[derive(Default)]
+struct Counter {
+    bits: [u64; 4],
+}
+
+impl Counter {
+    fn add(&mut self, word: u64) {
+        let mut carry = word;
+        for bit in &mut self.bits {
+            let next_carry = *bit & carry;
+            *bit ^= carry;
+            carry = next_carry;
+        }
+    }
+
+    /// Mask with the cells whose counter is the given value.
+    fn equals(&self, value: usize) -> u64 {
+        self.bits
+            .iter()
+            .enumerate()
+            .fold(u64::MAX, |mask, (index, bit)| {
+                if value & (1 << index) == 0 {
+                    mask & !bit
+                } else {
+                    mask & bit
+                }
+            })
+    }
+
+    fn equals_any(&self, values: &[usize]) -> u64 {
+        values
+            .iter()
+            .fold(0, |mask, value| mask | self.equals(*value))
+    }
+}
+
+/// It calculates consecutive rows, from `first_row`, into `next_rows`, with
+/// the words of one row after the other. The three neighbour rows slide
+/// down the grid, so every row is only unpacked once.
+fn calculate_next_rows(
+    grid: &Grid,
+    first_row: usize,
+    rule_masks: &RuleMasks,
+    next_rows: &mut [u64],
+) {
+    let first_row = i64::try_from(first_row).expect("the row should fit in an i64");
+    let mut above = NeighborRow::new(grid, first_row - 1);
+    let mut same = NeighborRow::new(grid, first_row);
+
+    for (row, next_row) in (first_row..).zip(next_rows.chunks_mut(grid.cells().words_per_row())) {
+        let below = NeighborRow::new(grid, row + 1);
+
+        calculate_next_row(&above, &same, &below, rule_masks, next_row);
+        if let Some(last_word) = next_row.last_mut() {
+            *last_word &= grid.cells().last_word_mask();
+        }
+
+        above = mem::replace(&mut same, below);
+    }
+}
+
+fn calculate_next_row(
+    above: &NeighborRow,
+    same: &NeighborRow,
+    below: &NeighborRow,
+    rule_masks: &RuleMasks,
+    next_row: &mut [u64],
+) {
+    for (word, next_word) in next_row.iter_mut().enumerate() {
+        let mut counter = Counter::default();
+        for neighbors in [
+            above.west[word],
+            above.center[word],
+            above.east[word],
+            same.west[word],
+            same.east[word],
+            below.west[word],
+            below.center[word],
+            below.east[word],
+        ] {
+            counter.add(neighbors);
+        }
+
+        let live = same.center[word];
+        let born = !live & counter.equals_any(&rule_masks.birth);
+        let survive = live & counter.equals_any(&rule_masks.survival);
+
+        *next_word = born | survive;
     }
 }
 
@@ -49,15 +278,38 @@ mod tests {
     use std::str::FromStr;
 
     use crate::domain::{
-        cell::coordinates::Coordinates,
-        grid::{functions::next_generation::next_generation, Grid},
+        cell::{coordinates::Coordinates, Cell},
+        grid::{
+            ages::{Age, CellAges},
+            functions::next_generation::{
+                next_generation, next_generation_cell_by_cell, next_generation_in_parallel,
+            },
+            topology::Topology,
+            Grid,
+        },
+        rule::Rule,
     };
 
+    /// Grid with pseudo-random cells
+    fn random_grid(rows: usize, columns: usize, seed: u64) -> Grid {
+        let mut grid = Grid::of_dead_cells(rows, columns);
+        let mut state = seed;
+        for cell_coordinates in grid.iter() {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            if state >> 62 == 0 {
+                grid.set_cell(&cell_coordinates, &Cell::live());
+            }
+        }
+        grid
+    }
+
     #[test]
     fn the_next_generation_of_cells_in_an_empty_grid_is_an_empty_grid() {
         let grid = Grid::new_empty();
 
-        assert_eq!(next_generation(&grid), Grid::new_empty());
+        assert_eq!(next_generation(&grid, &Rule::default()), Grid::new_empty());
     }
 
     #[test]
@@ -69,7 +321,7 @@ mod tests {
         )
         .unwrap();
 
-        assert!(next_generation(&grid)
+        assert!(next_generation(&grid, &Rule::default())
             .get_cell(&Coordinates::new(1, 1))
             .is_dead());
 
@@ -80,7 +332,7 @@ mod tests {
         )
         .unwrap();
 
-        assert!(next_generation(&grid)
+        assert!(next_generation(&grid, &Rule::default())
             .get_cell(&Coordinates::new(1, 1))
             .is_dead());
     }
@@ -94,7 +346,7 @@ mod tests {
         )
         .unwrap();
 
-        assert!(next_generation(&grid)
+        assert!(next_generation(&grid, &Rule::default())
             .get_cell(&Coordinates::new(1, 1))
             .is_live());
 
@@ -105,7 +357,7 @@ mod tests {
         )
         .unwrap();
 
-        assert!(next_generation(&grid)
+        assert!(next_generation(&grid, &Rule::default())
             .get_cell(&Coordinates::new(1, 1))
             .is_live());
     }
@@ -119,7 +371,7 @@ mod tests {
         )
         .unwrap();
 
-        assert!(next_generation(&grid)
+        assert!(next_generation(&grid, &Rule::default())
             .get_cell(&Coordinates::new(1, 1))
             .is_dead());
 
@@ -130,7 +382,7 @@ mod tests {
         )
         .unwrap();
 
-        assert!(next_generation(&grid)
+        assert!(next_generation(&grid, &Rule::default())
             .get_cell(&Coordinates::new(1, 1))
             .is_dead());
     }
@@ -144,7 +396,7 @@ mod tests {
         )
         .unwrap();
 
-        assert!(next_generation(&grid)
+        assert!(next_generation(&grid, &Rule::default())
             .get_cell(&Coordinates::new(1, 1))
             .is_live());
 
@@ -155,7 +407,7 @@ mod tests {
         )
         .unwrap();
 
-        assert!(next_generation(&grid)
+        assert!(next_generation(&grid, &Rule::default())
             .get_cell(&Coordinates::new(1, 1))
             .is_dead());
     }
@@ -176,8 +428,130 @@ mod tests {
         )
         .unwrap();
 
-        let actual_grid = next_generation(&grid);
+        let actual_grid = next_generation(&grid, &Rule::default());
+
+        assert_eq!(actual_grid, expected_grid);
+    }
+
+    #[test]
+    fn grid_edges_are_not_stitched_together_on_a_plane() {
+        let grid = Grid::from_str(
+            "⬛⬛⬛⬛⬛
+             ⬛⬛⬛⬛⬛
+             ⬛⬜⬜⬜⬛",
+        )
+        .unwrap()
+        .with_topology(Topology::Plane);
+
+        let expected_grid = Grid::from_str(
+            "⬛⬛⬛⬛⬛
+             ⬛⬛⬜⬛⬛
+             ⬛⬛⬜⬛⬛",
+        )
+        .unwrap()
+        .with_topology(Topology::Plane);
+
+        let actual_grid = next_generation(&grid, &Rule::default());
 
         assert_eq!(actual_grid, expected_grid);
     }
+
+    #[test]
+    fn it_should_apply_the_given_rule() {
+        // HighLife (B36/S23): a dead cell with six live neighbours is born
+        let grid = Grid::from_str(
+            "⬜⬜⬜
+             ⬛⬛⬛
+             ⬜⬜⬜",
+        )
+        .unwrap();
+
+        let high_life: Rule = "B36/S23".parse().unwrap();
+
+        assert!(next_generation(&grid, &high_life)
+            .get_cell(&Coordinates::new(1, 1))
+            .is_live());
+        assert!(next_generation(&grid, &Rule::conway())
+            .get_cell(&Coordinates::new(1, 1))
+            .is_dead());
+    }
+
+    #[test]
+    fn it_should_give_the_same_result_as_the_cell_by_cell_calculation_on_any_topology() {
+        for topology in [
+            Topology::Plane,
+            Topology::Torus,
+            Topology::HorizontalCylinder,
+            Topology::VerticalCylinder,
+            Topology::KleinBottle,
+            Topology::CrossSurface,
+        ] {
+            for (rows, columns) in [(1, 3), (2, 2), (7, 64), (13, 130)] {
+                let grid = random_grid(rows, columns, 42).with_topology(topology);
+
+                assert_eq!(
+                    next_generation(&grid, &Rule::default()),
+                    next_generation_cell_by_cell(&grid, &Rule::default()),
+                    "{rows}x{columns} grid on a {topology}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_give_the_same_result_using_several_threads() {
+        for topology in [Topology::Plane, Topology::Torus, Topology::KleinBottle] {
+            let grid = random_grid(37, 150, 3).with_topology(topology);
+
+            for threads in [2, 3, 8, 100] {
+                assert_eq!(
+                    next_generation_in_parallel(&grid, &Rule::default(), threads),
+                    next_generation(&grid, &Rule::default()),
+                    "{threads} threads on a {topology}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn the_cell_ages_should_be_carried_to_the_next_generation() {
+        let blinker = Grid::from_str(
+            "⬛⬛⬛⬛⬛
+             ⬛⬛⬛⬛⬛
+             ⬛⬜⬜⬜⬛
+             ⬛⬛⬛⬛⬛
+             ⬛⬛⬛⬛⬛",
+        )
+        .unwrap();
+        let grid = blinker.clone().with_ages(CellAges::new(&blinker));
+
+        let grid = next_generation(&grid, &Rule::default());
+        let grid = next_generation_in_parallel(&grid, &Rule::default(), 2);
+
+        // The center of the blinker never dies, its ends die and are born again
+        assert_eq!(grid.cell_with_age(&Coordinates::new(2, 2)).age(), Some(3));
+        assert_eq!(grid.cell_with_age(&Coordinates::new(2, 1)).age(), Some(1));
+        assert_eq!(
+            grid.ages().map(|ages| ages.age(&Coordinates::new(1, 2))),
+            Some(Age::Dead(1))
+        );
+        // The ages do not make the grids different
+        assert_eq!(grid, blinker);
+    }
+
+    #[test]
+    fn the_cell_ages_should_only_be_tracked_when_asked_for() {
+        let grid = next_generation(&Grid::of_live_cells(2, 2), &Rule::default());
+
+        assert_eq!(grid.ages(), None);
+        assert_eq!(grid.cell_with_age(&Coordinates::new(0, 0)).age(), None);
+    }
+
+    #[test]
+    fn the_next_generation_of_an_empty_grid_calculated_in_parallel_is_an_empty_grid() {
+        assert_eq!(
+            next_generation_in_parallel(&Grid::new_empty(), &Rule::default(), 4),
+            Grid::new_empty()
+        );
+    }
 }
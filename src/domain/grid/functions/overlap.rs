@@ -40,13 +40,15 @@ use crate::domain::{cell::coordinates::Coordinates, grid::Grid};
 /// 4⬛⬛⬜⬜⬜
 /// ```
 ///
+/// The new grid keeps the back grid topology.
+///
 /// # Panics
 ///
 /// Will panic if the front grid does not fit totally inside the back grid at the given position.
 #[must_use]
 pub fn overlap(back_grid: &Grid, front_grid: &Grid, front_grid_position: &Coordinates) -> Grid {
     if back_grid.is_empty() {
-        return Grid::new_empty();
+        return Grid::new_empty().with_topology(back_grid.topology());
     }
 
     if front_grid.is_empty() {
@@ -54,7 +56,7 @@ pub fn overlap(back_grid: &Grid, front_grid: &Grid, front_grid_position: &Coordi
     }
 
     if perfect_overlapping(back_grid, front_grid, front_grid_position) {
-        return front_grid.clone();
+        return front_grid.clone().with_topology(back_grid.topology());
     }
 
     assert!(
@@ -74,6 +76,7 @@ pub fn overlap(back_grid: &Grid, front_grid: &Grid, front_grid_position: &Coordi
         front_grid,
         front_grid_position,
     ))
+    .with_topology(back_grid.topology())
 }
 
 fn perfect_overlapping(
@@ -180,7 +183,7 @@ fn relative_front_grid_cell_coordinates(
 mod tests {
     use crate::{
         domain::cell::coordinates::Coordinates, domain::grid::functions::overlap::overlap,
-        domain::grid::Grid,
+        domain::grid::topology::Topology, domain::grid::Grid,
     };
 
     #[test]
@@ -290,6 +293,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn it_should_keep_the_back_grid_topology() {
+        let back_grid = Grid::of_dead_cells(5, 5).with_topology(Topology::Plane);
+        let front_grid = Grid::of_live_cells(3, 3);
+
+        assert_eq!(
+            overlap(&back_grid, &front_grid, &Coordinates::new(2, 2)).topology(),
+            Topology::Plane
+        );
+    }
+
     mod overlapping_not_on_the_left_top_corner {
         use std::str::FromStr;
 
@@ -0,0 +1,162 @@
+use crate::domain::{
+    cell::coordinates::Coordinates,
+    grid::{bits::BitRows, bounding_box::BoundingBox, Grid},
+};
+
+/// It rotates the grid 90 degrees clockwise. The rows of the new grid are
+/// the columns of the original one.
+#[must_use]
+pub fn rotate_clockwise(grid: &Grid) -> Grid {
+    transform(grid, grid.columns(), grid.rows(), |row, column| {
+        Coordinates::new(grid.rows() - 1 - column, row)
+    })
+}
+
+/// It mirrors the grid left to right.
+#[must_use]
+pub fn flip_horizontally(grid: &Grid) -> Grid {
+    transform(grid, grid.rows(), grid.columns(), |row, column| {
+        Coordinates::new(row, grid.columns() - 1 - column)
+    })
+}
+
+/// It mirrors the grid top to bottom.
+#[must_use]
+pub fn flip_vertically(grid: &Grid) -> Grid {
+    transform(grid, grid.rows(), grid.columns(), |row, column| {
+        Coordinates::new(grid.rows() - 1 - row, column)
+    })
+}
+
+/// It removes the rows and columns around the live cells. A grid without
+/// live cells is returned as it is.
+#[must_use]
+pub fn crop(grid: &Grid) -> Grid {
+    let Some(bounding_box) = BoundingBox::of(grid) else {
+        return grid.clone();
+    };
+
+    transform(
+        grid,
+        bounding_box.rows(),
+        bounding_box.columns(),
+        |row, column| Coordinates::new(bounding_box.top + row, bounding_box.left + column),
+    )
+}
+
+/// It builds a grid with the given size where every cell takes the state of
+/// the original grid cell at the coordinates returned by `source`.
+fn transform(
+    grid: &Grid,
+    rows: usize,
+    columns: usize,
+    source: impl Fn(usize, usize) -> Coordinates,
+) -> Grid {
+    let mut cells = BitRows::new(rows, columns);
+
+    for row in 0..rows {
+        for column in 0..columns {
+            cells.set(row, column, grid.is_live(&source(row, column)));
+        }
+    }
+
+    Grid::with_cells(cells).with_topology(grid.topology())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::domain::grid::{
+        functions::transform::{crop, flip_horizontally, flip_vertically, rotate_clockwise},
+        Grid,
+    };
+
+    fn grid(text: &str) -> Grid {
+        Grid::from_str(text).unwrap()
+    }
+
+    #[test]
+    fn it_should_rotate_the_grid_clockwise() {
+        let rotated = rotate_clockwise(&grid(
+            "⬜⬜⬜
+             ⬜⬛⬛",
+        ));
+
+        assert_eq!(
+            rotated,
+            grid(
+                "⬜⬜
+                 ⬛⬜
+                 ⬛⬜"
+            )
+        );
+    }
+
+    #[test]
+    fn it_should_flip_the_grid_horizontally() {
+        assert_eq!(
+            flip_horizontally(&grid(
+                "⬜⬛⬛
+                 ⬜⬜⬛"
+            )),
+            grid(
+                "⬛⬛⬜
+                 ⬛⬜⬜"
+            )
+        );
+    }
+
+    #[test]
+    fn it_should_flip_the_grid_vertically() {
+        assert_eq!(
+            flip_vertically(&grid(
+                "⬜⬛⬛
+                 ⬜⬜⬛"
+            )),
+            grid(
+                "⬜⬜⬛
+                 ⬜⬛⬛"
+            )
+        );
+    }
+
+    #[test]
+    fn four_rotations_should_leave_the_grid_as_it_was() {
+        let glider = grid(
+            "⬛⬜⬛
+             ⬛⬛⬜
+             ⬜⬜⬜",
+        );
+
+        let rotated = (0..4).fold(glider.clone(), |grid, _| rotate_clockwise(&grid));
+
+        assert_eq!(rotated, glider);
+    }
+
+    #[test]
+    fn it_should_crop_the_grid_to_the_live_cells() {
+        assert_eq!(
+            crop(&grid(
+                "⬛⬛⬛⬛
+                 ⬛⬜⬛⬛
+                 ⬛⬛⬜⬛
+                 ⬛⬛⬛⬛"
+            )),
+            grid(
+                "⬜⬛
+                 ⬛⬜"
+            )
+        );
+    }
+
+    #[test]
+    fn a_grid_without_live_cells_should_not_be_cropped() {
+        let dead = grid(
+            "⬛⬛
+             ⬛⬛",
+        );
+
+        assert_eq!(crop(&dead), dead);
+    }
+}
@@ -1,22 +1,54 @@
+pub mod ages;
+pub mod bits;
+pub mod bounding_box;
 pub mod functions;
+pub mod placement;
 pub mod size;
+pub mod topology;
 pub mod traverser;
 
+use std::error::Error;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::str::FromStr;
 
 use std::fmt::Write;
 
 use super::cell::row::Row;
+use crate::domain::cell::alphabet::Alphabet;
 use crate::domain::cell::coordinates::Coordinates;
 use crate::domain::cell::state::State;
 use crate::domain::cell::Cell;
+use crate::domain::grid::ages::CellAges;
+use crate::domain::grid::bits::BitRows;
 use crate::domain::grid::size::Size;
+use crate::domain::grid::topology::Topology;
 use crate::domain::grid::traverser::Traverser;
 
-#[derive(PartialEq, Debug, Clone)]
+/// Cells returned by reference for the bit-packed cell states
+const LIVE_CELL: Cell = Cell::live();
+const DEAD_CELL: Cell = Cell::dead();
+
+#[derive(Debug, Clone)]
 pub struct Grid {
-    pub cell_rows: Vec<Row>,
+    cells: BitRows,
+    topology: Topology,
+    /// Age of every cell, when it is tracked.
+    ages: Option<CellAges>,
+}
+
+/// Grids with the same cells are equal, whatever the ages of the cells.
+impl PartialEq for Grid {
+    fn eq(&self, other: &Self) -> bool {
+        self.cells == other.cells && self.topology == other.topology
+    }
+}
+
+impl Hash for Grid {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.cells.hash(state);
+        self.topology.hash(state);
+    }
 }
 
 /// Info needed to calculate the cell state in the next generation
@@ -100,44 +132,73 @@ impl Default for Grid {
     }
 }
 
+/// Error parsing a text pattern. Lines and columns start at 1.
 #[derive(Debug, PartialEq, Eq)]
-pub struct ParseGridError;
+pub enum ParseGridError {
+    /// The char is not one of the chars for live and dead cells.
+    InvalidChar {
+        line: usize,
+        column: usize,
+        invalid_char: char,
+    },
+    /// The row does not have the same number of cells as the first one.
+    RaggedRow {
+        line: usize,
+        expected_cells: usize,
+        found_cells: usize,
+    },
+}
+
+impl Error for ParseGridError {}
+
+impl fmt::Display for ParseGridError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseGridError::InvalidChar {
+                line,
+                column,
+                invalid_char,
+            } => write!(
+                f,
+                "Invalid char '{invalid_char}' for cell state at line {line}, column {column}"
+            ),
+            ParseGridError::RaggedRow {
+                line,
+                expected_cells,
+                found_cells,
+            } => write!(
+                f,
+                "The row at line {line} has {found_cells} cells but the first row has {expected_cells}"
+            ),
+        }
+    }
+}
 
 impl FromStr for Grid {
     type Err = ParseGridError;
 
     fn from_str(text: &str) -> Result<Self, Self::Err> {
-        let trimmed_text = text.trim();
-
-        if trimmed_text.is_empty() {
-            return Ok(Grid::new_empty());
-        }
-
-        let mut cell_rows = vec![];
-
-        for line in trimmed_text.lines() {
-            cell_rows.push(line.parse().unwrap());
-        }
-
-        Ok(Grid::new(cell_rows))
+        Self::parse_with(text, &Alphabet::default())
     }
 }
 
 impl fmt::Display for Grid {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", display(self))
+        write!(f, "{}", display(self, Alphabet::default()))
     }
 }
 
-fn display(grid: &Grid) -> String {
+fn display(grid: &Grid, alphabet: Alphabet) -> String {
     let mut output = String::new();
 
-    for cell_row in &grid.cell_rows {
-        write!(&mut output, "{cell_row}").unwrap();
+    for row in 0..grid.rows() {
+        for column in 0..grid.columns() {
+            output.push(alphabet.char_for(&grid.get_cell(&Coordinates::new(row, column)).state()));
+        }
         writeln!(&mut output).unwrap();
     }
 
-    output.to_string()
+    output
 }
 
 impl Grid {
@@ -146,37 +207,170 @@ impl Grid {
     /// Will panic if all the grid rows do not have the same amount of cells.
     #[must_use]
     pub fn new(cell_rows: Vec<Row>) -> Self {
-        if !cell_rows.is_empty() {
-            let first_row_len = cell_rows[0].len();
-            for cell_row in &cell_rows {
-                assert!(
-                    cell_row.len() == first_row_len,
-                    "Cell rows do not have the same length"
-                );
+        let columns = cell_rows.first().map_or(0, Row::len);
+
+        let mut grid = Self::of_dead_cells(cell_rows.len(), columns);
+
+        for (row, cell_row) in cell_rows.into_iter().enumerate() {
+            assert!(
+                cell_row.len() == columns,
+                "Cell rows do not have the same length"
+            );
+            for column in 0..columns {
+                if cell_row.get_cell(column).is_live() {
+                    grid.cells.set(row, column, true);
+                }
             }
         }
-        Self { cell_rows }
+
+        grid
+    }
+
+    /// It parses a grid written with the given alphabet, one line per row.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the text is not a valid grid.
+    pub fn parse_with(text: &str, alphabet: &Alphabet) -> Result<Self, ParseGridError> {
+        let lines: Vec<&str> = text.lines().collect();
+        let is_blank = |line: &str| line.trim().is_empty();
+
+        let Some(first_line) = lines.iter().position(|line| !is_blank(line)) else {
+            return Ok(Grid::new_empty());
+        };
+        let last_line = lines.len() - lines.iter().rev().take_while(|line| is_blank(line)).count();
+
+        let mut cell_rows: Vec<Vec<bool>> = vec![];
+
+        for (index, line) in lines.iter().enumerate().take(last_line).skip(first_line) {
+            let line_number = index + 1;
+            let leading_spaces = line.chars().take_while(|c| c.is_whitespace()).count();
+
+            let mut cell_row = vec![];
+            for (position, c) in line.trim().chars().enumerate() {
+                let state = alphabet
+                    .state_for(c)
+                    .map_err(|_| ParseGridError::InvalidChar {
+                        line: line_number,
+                        column: leading_spaces + position + 1,
+                        invalid_char: c,
+                    })?;
+                cell_row.push(state == State::Live);
+            }
+
+            if let Some(first_row) = cell_rows.first() {
+                if cell_row.len() != first_row.len() {
+                    return Err(ParseGridError::RaggedRow {
+                        line: line_number,
+                        expected_cells: first_row.len(),
+                        found_cells: cell_row.len(),
+                    });
+                }
+            }
+
+            cell_rows.push(cell_row);
+        }
+
+        let mut cells = BitRows::new(cell_rows.len(), cell_rows[0].len());
+        for (row, cell_row) in cell_rows.iter().enumerate() {
+            for (column, live) in cell_row.iter().enumerate() {
+                cells.set(row, column, *live);
+            }
+        }
+
+        Ok(Self::with_cells(cells))
+    }
+
+    /// It renders the grid with the given alphabet, one line per row.
+    #[must_use]
+    pub fn to_string_with(&self, alphabet: &Alphabet) -> String {
+        display(self, *alphabet)
     }
 
     #[must_use]
     pub fn new_empty() -> Self {
-        Self { cell_rows: vec![] }
+        Self::new(vec![])
     }
 
     #[must_use]
     pub fn of_dead_cells(rows: usize, columns: usize) -> Self {
-        Self {
-            cell_rows: vec![Row::of_dead_cells(columns); rows],
-        }
+        let columns = if rows == 0 { 0 } else { columns };
+
+        Self::with_cells(BitRows::new(rows, columns))
     }
 
     #[must_use]
     pub fn of_live_cells(rows: usize, columns: usize) -> Self {
+        let mut grid = Self::of_dead_cells(rows, columns);
+        for cell_coordinates in grid.iter() {
+            grid.cells
+                .set(cell_coordinates.row, cell_coordinates.column, true);
+        }
+        grid
+    }
+
+    /// It builds a grid directly from its bit-packed storage.
+    #[must_use]
+    pub fn with_cells(cells: BitRows) -> Self {
         Self {
-            cell_rows: vec![Row::of_live_cells(columns); rows],
+            cells,
+            topology: Topology::default(),
+            ages: None,
         }
     }
 
+    /// Bit-packed storage with the cell states.
+    #[must_use]
+    pub fn cells(&self) -> &BitRows {
+        &self.cells
+    }
+
+    /// It returns the same grid but with the edges stitched together
+    /// following the given topology.
+    #[must_use]
+    pub fn with_topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    #[must_use]
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    /// It returns the same grid tracking the age of its cells, starting
+    /// from the given ages.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the ages are not for a grid with the same size.
+    #[must_use]
+    pub fn with_ages(mut self, ages: CellAges) -> Self {
+        assert!(
+            ages.rows() == self.rows() && ages.columns() == self.columns(),
+            "The ages should have the grid size"
+        );
+        self.ages = Some(ages);
+        self
+    }
+
+    /// Age of every cell, if it is tracked.
+    #[must_use]
+    pub fn ages(&self) -> Option<&CellAges> {
+        self.ages.as_ref()
+    }
+
+    /// It takes the ages of the previous generation updated with the cells
+    /// of this one, if the previous generation tracked them.
+    #[must_use]
+    pub(crate) fn with_ages_after(mut self, previous: &Grid) -> Self {
+        self.ages = previous.ages.clone().map(|mut ages| {
+            ages.update(&self);
+            ages
+        });
+        self
+    }
+
     #[must_use]
     pub fn iter(&self) -> Traverser {
         Traverser::new(self.size())
@@ -189,15 +383,12 @@ impl Grid {
 
     #[must_use]
     pub fn rows(&self) -> usize {
-        self.cell_rows.len()
+        self.cells.rows()
     }
 
     #[must_use]
     pub fn columns(&self) -> usize {
-        if self.cell_rows.is_empty() {
-            return 0;
-        }
-        self.cell_rows[0].len()
+        self.cells.columns()
     }
 
     #[must_use]
@@ -210,9 +401,57 @@ impl Grid {
         self.number_of_cells() == 0
     }
 
+    /// Number of live cells.
+    #[must_use]
+    pub fn population(&self) -> usize {
+        self.cells.count_live()
+    }
+
+    #[must_use]
+    pub fn is_live(&self, cell_coordinates: &Coordinates) -> bool {
+        self.cells
+            .get(cell_coordinates.row, cell_coordinates.column)
+    }
+
     #[must_use]
     pub fn get_cell(&self, cell_coordinates: &Coordinates) -> &Cell {
-        self.cell_rows[cell_coordinates.row].get_cell(cell_coordinates.column)
+        if self.is_live(cell_coordinates) {
+            &LIVE_CELL
+        } else {
+            &DEAD_CELL
+        }
+    }
+
+    /// The cell at the given coordinates with its age, if it is tracked.
+    #[must_use]
+    pub fn cell_with_age(&self, cell_coordinates: &Coordinates) -> Cell {
+        match &self.ages {
+            Some(ages) => ages.cell(cell_coordinates),
+            None => self.get_cell(cell_coordinates).clone(),
+        }
+    }
+
+    /// Rows of cells, as they were stored before the cells were bit-packed.
+    /// It copies every cell, use `is_live` or `cells` on hot paths.
+    #[must_use]
+    pub fn cell_rows(&self) -> Vec<Row> {
+        (0..self.rows())
+            .map(|row| {
+                Row::with(
+                    (0..self.columns())
+                        .map(|column| self.get_cell(&Coordinates::new(row, column)).clone())
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
+    pub fn set_cell(&mut self, cell_coordinates: &Coordinates, cell: &Cell) {
+        self.cells.set(
+            cell_coordinates.row,
+            cell_coordinates.column,
+            cell.is_live(),
+        );
     }
 
     #[must_use]
@@ -257,52 +496,30 @@ impl Grid {
         cell_coordinates.column as i64 == self.last_column()
     }
 
-    fn get_neighbors(&self, cell_coordinates: &Coordinates) -> Vec<&Cell> {
+    fn get_neighbors(&self, cell_coordinates: &Coordinates) -> Vec<Cell> {
         neighbors()
             .iter()
             .map(|neighbor| self.get_neighbor(cell_coordinates, neighbor))
             .collect()
     }
 
-    fn get_neighbor(&self, cell_coordinate: &Coordinates, neighbor: &Neighbor) -> &Cell {
-        self.get_cell(
-            &self.cell_coordinate_translate(cell_coordinate, &NeighborDistance::new(neighbor)),
-        )
+    fn get_neighbor(&self, cell_coordinate: &Coordinates, neighbor: &Neighbor) -> Cell {
+        match self.cell_coordinate_translate(cell_coordinate, &NeighborDistance::new(neighbor)) {
+            Some(neighbor_coordinates) => self.get_cell(&neighbor_coordinates).clone(),
+            None => DEAD_CELL,
+        }
     }
 
-    /// It handles toroidal array positions
+    /// It handles the grid edges depending on the grid topology
     fn cell_coordinate_translate(
         &self,
         cell_coordinates: &Coordinates,
         distance: &NeighborDistance,
-    ) -> Coordinates {
-        let mut new_row = cell_coordinates.row as i64 + distance.row_distance;
-        let mut new_column = cell_coordinates.column as i64 + distance.column_distance;
-
-        if new_row < 0 {
-            new_row = self.last_row();
-        }
-
-        if new_row > self.last_row() {
-            new_row = 0;
-        }
+    ) -> Option<Coordinates> {
+        let new_row = cell_coordinates.row as i64 + distance.row_distance;
+        let new_column = cell_coordinates.column as i64 + distance.column_distance;
 
-        if new_column < 0 {
-            new_column = self.last_column();
-        }
-
-        if new_column > self.last_column() {
-            new_column = 0;
-        }
-
-        let row = usize::try_from(new_row).unwrap();
-        let column = usize::try_from(new_column).unwrap();
-
-        Coordinates::new(row, column)
-    }
-
-    fn last_row(&self) -> i64 {
-        self.rows() as i64 - 1
+        self.topology.resolve(new_row, new_column, &self.size())
     }
 
     fn last_column(&self) -> i64 {
@@ -317,7 +534,7 @@ mod tests {
 
     use crate::{
         domain::cell::{coordinates::Coordinates, row::Row, state::State, Cell},
-        domain::grid::{size::Size, CellInfo, Grid},
+        domain::grid::{size::Size, topology::Topology, CellInfo, Grid},
     };
 
     #[test]
@@ -375,6 +592,37 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn a_grid_should_return_the_cell_rows_it_was_built_with() {
+        let cell_rows = vec![
+            Row::new(vec![Cell::live(), Cell::dead()]),
+            Row::new(vec![Cell::dead(), Cell::live()]),
+        ];
+
+        assert_eq!(Grid::new(cell_rows.clone()).cell_rows(), cell_rows);
+    }
+
+    #[test]
+    fn a_cell_can_be_changed() {
+        let mut grid = Grid::of_dead_cells(2, 2);
+
+        grid.set_cell(&Coordinates::new(1, 0), &Cell::live());
+
+        assert!(grid.is_live(&Coordinates::new(1, 0)));
+        assert!(grid.get_cell(&Coordinates::new(1, 0)).is_live());
+    }
+
+    #[test]
+    fn it_should_return_the_number_of_live_cells() {
+        let grid = Grid::from_str(
+            "⬜⬛⬜
+             ⬛⬛⬜",
+        )
+        .unwrap();
+
+        assert_eq!(grid.population(), 3);
+    }
+
     #[test]
     fn a_2x3_grid_contains_six_cells() {
         let grid = Grid::from_str(
@@ -599,6 +847,168 @@ mod tests {
         }
     }
 
+    mod the_edges_are_not_stitched_together_on_a_plane {
+        use std::str::FromStr;
+
+        use crate::{
+            domain::cell::coordinates::Coordinates,
+            domain::grid::{topology::Topology, Grid, Neighbor},
+        };
+
+        #[test]
+        fn neighbors_outside_the_grid_are_dead() {
+            let grid = Grid::from_str(
+                "⬛⬛⬛
+                 ⬛⬛⬛
+                 ⬛⬛⬜",
+            )
+            .unwrap()
+            .with_topology(Topology::Plane);
+
+            assert!(grid
+                .get_neighbor(&Coordinates::new(0, 0), &Neighbor::LetTop)
+                .is_dead());
+        }
+
+        #[test]
+        fn a_cell_in_the_corner_has_only_three_neighbors() {
+            let grid = Grid::of_live_cells(3, 3).with_topology(Topology::Plane);
+
+            assert_eq!(
+                grid.number_of_live_neighbors_for(&Coordinates::new(0, 0)),
+                3
+            );
+        }
+    }
+
+    mod on_a_horizontal_cylinder {
+        use std::str::FromStr;
+
+        use crate::{
+            domain::cell::coordinates::Coordinates,
+            domain::grid::{topology::Topology, Grid, Neighbor},
+        };
+
+        #[test]
+        fn only_the_left_and_right_edges_are_stitched_together() {
+            let grid = Grid::from_str(
+                "⬛⬛⬜
+                 ⬛⬛⬛
+                 ⬜⬛⬛",
+            )
+            .unwrap()
+            .with_topology(Topology::HorizontalCylinder);
+
+            assert!(grid
+                .get_neighbor(&Coordinates::new(0, 0), &Neighbor::Left)
+                .is_live());
+            assert!(grid
+                .get_neighbor(&Coordinates::new(0, 0), &Neighbor::Top)
+                .is_dead());
+        }
+    }
+
+    mod on_a_vertical_cylinder {
+        use std::str::FromStr;
+
+        use crate::{
+            domain::cell::coordinates::Coordinates,
+            domain::grid::{topology::Topology, Grid, Neighbor},
+        };
+
+        #[test]
+        fn only_the_top_and_bottom_edges_are_stitched_together() {
+            let grid = Grid::from_str(
+                "⬛⬛⬜
+                 ⬛⬛⬛
+                 ⬜⬛⬛",
+            )
+            .unwrap()
+            .with_topology(Topology::VerticalCylinder);
+
+            assert!(grid
+                .get_neighbor(&Coordinates::new(0, 0), &Neighbor::Top)
+                .is_live());
+            assert!(grid
+                .get_neighbor(&Coordinates::new(0, 0), &Neighbor::Left)
+                .is_dead());
+        }
+    }
+
+    mod on_a_klein_bottle {
+        use std::str::FromStr;
+
+        use crate::{
+            domain::cell::coordinates::Coordinates,
+            domain::grid::{topology::Topology, Grid, Neighbor},
+        };
+
+        #[test]
+        fn the_top_and_bottom_edges_are_stitched_together_with_a_twist() {
+            let grid = Grid::from_str(
+                "⬛⬛⬛
+                 ⬛⬛⬛
+                 ⬛⬛⬜",
+            )
+            .unwrap()
+            .with_topology(Topology::KleinBottle);
+
+            assert!(grid
+                .get_neighbor(&Coordinates::new(0, 0), &Neighbor::Top)
+                .is_live());
+            assert!(grid
+                .get_neighbor(&Coordinates::new(2, 2), &Neighbor::Bottom)
+                .is_dead());
+        }
+
+        #[test]
+        fn the_left_and_right_edges_are_stitched_together_without_a_twist() {
+            let grid = Grid::from_str(
+                "⬛⬛⬛
+                 ⬛⬛⬜
+                 ⬛⬛⬛",
+            )
+            .unwrap()
+            .with_topology(Topology::KleinBottle);
+
+            assert!(grid
+                .get_neighbor(&Coordinates::new(1, 0), &Neighbor::Left)
+                .is_live());
+        }
+    }
+
+    mod on_a_cross_surface {
+        use std::str::FromStr;
+
+        use crate::{
+            domain::cell::coordinates::Coordinates,
+            domain::grid::{topology::Topology, Grid, Neighbor},
+        };
+
+        #[test]
+        fn both_pairs_of_edges_are_stitched_together_with_a_twist() {
+            let grid = Grid::from_str(
+                "⬛⬛⬛
+                 ⬛⬛⬛
+                 ⬛⬛⬜",
+            )
+            .unwrap()
+            .with_topology(Topology::CrossSurface);
+
+            assert!(grid
+                .get_neighbor(&Coordinates::new(0, 0), &Neighbor::Top)
+                .is_live());
+            assert!(grid
+                .get_neighbor(&Coordinates::new(0, 0), &Neighbor::Left)
+                .is_live());
+        }
+    }
+
+    #[test]
+    fn a_grid_is_a_torus_by_default() {
+        assert_eq!(Grid::of_dead_cells(2, 2).topology(), Topology::Torus);
+    }
+
     #[test]
     fn it_should_calculate_the_number_of_live_neighbors() {
         let grid = Grid::from_str(
@@ -744,7 +1154,7 @@ mod tests {
 
     mod for_displaying {
         use crate::{
-            domain::cell::{row::Row, Cell},
+            domain::cell::{alphabet::Alphabet, row::Row, Cell},
             domain::grid::Grid,
         };
 
@@ -779,12 +1189,24 @@ mod tests {
 
             assert_eq!(grid.to_string(), "⬜⬜⬜\n⬛⬛⬛\n⬜⬜⬜\n");
         }
+
+        #[test]
+        fn it_should_render_a_grid_with_another_alphabet() {
+            let grid = Grid::new(vec![
+                Row::new(vec![Cell::live(), Cell::dead()]),
+                Row::new(vec![Cell::dead(), Cell::live()]),
+            ]);
+
+            assert_eq!(grid.to_string_with(&Alphabet::new('O', '.')), "O.\n.O\n");
+        }
     }
 
     mod for_instantiation_from_string {
+        use std::str::FromStr;
+
         use crate::{
-            domain::cell::{c, row::Row},
-            domain::grid::Grid,
+            domain::cell::{alphabet::Alphabet, c, row::Row},
+            domain::grid::{Grid, ParseGridError},
         };
 
         #[test]
@@ -817,8 +1239,63 @@ mod tests {
                     Row::new(vec![c('⬜'), c('⬜'), c('⬜')]),
                 ])
             );
+        }
 
-            // todo: parse error cases
+        #[test]
+        fn it_should_fail_with_the_position_of_an_invalid_char() {
+            assert_eq!(
+                Grid::from_str("⬜⬜⬜\n⬜X⬜").unwrap_err(),
+                ParseGridError::InvalidChar {
+                    line: 2,
+                    column: 2,
+                    invalid_char: 'X'
+                }
+            );
+        }
+
+        #[test]
+        fn lines_and_columns_of_errors_include_blank_lines_and_indentation() {
+            assert_eq!(
+                Grid::from_str("\n  ⬜⬜\n  ⬜*").unwrap_err(),
+                ParseGridError::InvalidChar {
+                    line: 3,
+                    column: 4,
+                    invalid_char: '*'
+                }
+            );
+        }
+
+        #[test]
+        fn it_should_fail_with_ragged_rows() {
+            assert_eq!(
+                Grid::from_str("⬜⬜⬜\n⬜⬜⬜\n⬜⬜").unwrap_err(),
+                ParseGridError::RaggedRow {
+                    line: 3,
+                    expected_cells: 3,
+                    found_cells: 2
+                }
+            );
+        }
+
+        #[test]
+        fn the_error_should_be_displayed_with_its_position() {
+            assert_eq!(
+                Grid::from_str("⬜X").unwrap_err().to_string(),
+                "Invalid char 'X' for cell state at line 1, column 2"
+            );
+        }
+
+        #[test]
+        fn it_should_be_converted_from_a_string_with_another_alphabet() {
+            let grid = Grid::parse_with("#.\n.#", &Alphabet::new('#', '.')).unwrap();
+
+            assert_eq!(
+                grid,
+                Grid::new(vec![
+                    Row::new(vec![c('⬜'), c('⬛')]),
+                    Row::new(vec![c('⬛'), c('⬜')]),
+                ])
+            );
         }
     }
 }
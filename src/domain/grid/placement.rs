@@ -0,0 +1,255 @@
+use std::{error::Error, fmt, str::FromStr};
+
+use crate::domain::cell::coordinates::Coordinates;
+
+use super::size::Size;
+
+/// Point of the background grid the pattern is aligned with.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Anchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    #[default]
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseAnchorError {
+    pub invalid_anchor: String,
+}
+
+impl Error for ParseAnchorError {}
+
+impl fmt::Display for ParseAnchorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid anchor '{}'", self.invalid_anchor)
+    }
+}
+
+const ANCHORS: [(Anchor, &str); 9] = [
+    (Anchor::TopLeft, "top-left"),
+    (Anchor::Top, "top"),
+    (Anchor::TopRight, "top-right"),
+    (Anchor::Left, "left"),
+    (Anchor::Center, "center"),
+    (Anchor::Right, "right"),
+    (Anchor::BottomLeft, "bottom-left"),
+    (Anchor::Bottom, "bottom"),
+    (Anchor::BottomRight, "bottom-right"),
+];
+
+impl FromStr for Anchor {
+    type Err = ParseAnchorError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        ANCHORS
+            .iter()
+            .find(|(_, name)| name.eq_ignore_ascii_case(text.trim()))
+            .map(|(anchor, _)| *anchor)
+            .ok_or_else(|| ParseAnchorError {
+                invalid_anchor: text.to_owned(),
+            })
+    }
+}
+
+impl fmt::Display for Anchor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (_, name) = ANCHORS
+            .iter()
+            .find(|(anchor, _)| anchor == self)
+            .expect("all anchors should have a name");
+        write!(f, "{name}")
+    }
+}
+
+/// Where the pattern is placed on the background grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Placement {
+    /// The pattern is aligned with a point of the grid, the center by default.
+    Anchor(Anchor),
+    /// The pattern left top corner is placed at the given coordinates.
+    Offset(Coordinates),
+}
+
+impl Default for Placement {
+    fn default() -> Self {
+        Placement::Anchor(Anchor::default())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct PatternDoesNotFitError {
+    pub pattern_rows: usize,
+    pub pattern_columns: usize,
+    pub grid_rows: usize,
+    pub grid_columns: usize,
+    pub position: Coordinates,
+}
+
+impl Error for PatternDoesNotFitError {}
+
+impl fmt::Display for PatternDoesNotFitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "The {}x{} pattern does not fit in the {}x{} grid at row {}, column {}",
+            self.pattern_rows,
+            self.pattern_columns,
+            self.grid_rows,
+            self.grid_columns,
+            self.position.row,
+            self.position.column
+        )
+    }
+}
+
+impl Placement {
+    /// Coordinates of the pattern left top corner on a grid with the given
+    /// size, even if the pattern does not fit. An anchored pattern bigger
+    /// than the grid starts at the first row or column.
+    #[must_use]
+    pub fn origin(&self, grid_size: &Size, pattern_size: &Size) -> Coordinates {
+        let anchor = match self {
+            Placement::Anchor(anchor) => anchor,
+            Placement::Offset(offset) => return *offset,
+        };
+
+        let free_rows = grid_size.rows.saturating_sub(pattern_size.rows);
+        let free_columns = grid_size.columns.saturating_sub(pattern_size.columns);
+
+        let row = match anchor {
+            Anchor::TopLeft | Anchor::Top | Anchor::TopRight => 0,
+            Anchor::Left | Anchor::Center | Anchor::Right => free_rows / 2,
+            Anchor::BottomLeft | Anchor::Bottom | Anchor::BottomRight => free_rows,
+        };
+        let column = match anchor {
+            Anchor::TopLeft | Anchor::Left | Anchor::BottomLeft => 0,
+            Anchor::Top | Anchor::Center | Anchor::Bottom => free_columns / 2,
+            Anchor::TopRight | Anchor::Right | Anchor::BottomRight => free_columns,
+        };
+
+        Coordinates::new(row, column)
+    }
+
+    /// Coordinates of the pattern left top corner on a grid with the given size.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the whole pattern does not fit inside the grid.
+    pub fn position(
+        &self,
+        grid_size: &Size,
+        pattern_size: &Size,
+    ) -> Result<Coordinates, PatternDoesNotFitError> {
+        let position = self.origin(grid_size, pattern_size);
+        // Offsets so big that the pattern end overflows do not fit either
+        let fits = |start: usize, length: usize, limit: usize| {
+            start.checked_add(length).is_some_and(|end| end <= limit)
+        };
+
+        if !fits(position.row, pattern_size.rows, grid_size.rows)
+            || !fits(position.column, pattern_size.columns, grid_size.columns)
+        {
+            return Err(PatternDoesNotFitError {
+                pattern_rows: pattern_size.rows,
+                pattern_columns: pattern_size.columns,
+                grid_rows: grid_size.rows,
+                grid_columns: grid_size.columns,
+                position,
+            });
+        }
+
+        Ok(position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::{
+        cell::coordinates::Coordinates,
+        grid::{
+            placement::{Anchor, ParseAnchorError, PatternDoesNotFitError, Placement},
+            size::Size,
+        },
+    };
+
+    fn position(placement: Placement) -> Coordinates {
+        placement
+            .position(&Size::new(10, 20), &Size::new(3, 4))
+            .unwrap()
+    }
+
+    #[test]
+    fn the_pattern_is_centered_by_default() {
+        assert_eq!(position(Placement::default()), Coordinates::new(3, 8));
+    }
+
+    #[test]
+    fn the_pattern_can_be_aligned_with_a_corner_or_an_edge() {
+        let anchored = |anchor| position(Placement::Anchor(anchor));
+
+        assert_eq!(anchored(Anchor::TopLeft), Coordinates::new(0, 0));
+        assert_eq!(anchored(Anchor::Top), Coordinates::new(0, 8));
+        assert_eq!(anchored(Anchor::TopRight), Coordinates::new(0, 16));
+        assert_eq!(anchored(Anchor::Left), Coordinates::new(3, 0));
+        assert_eq!(anchored(Anchor::Right), Coordinates::new(3, 16));
+        assert_eq!(anchored(Anchor::BottomLeft), Coordinates::new(7, 0));
+        assert_eq!(anchored(Anchor::Bottom), Coordinates::new(7, 8));
+        assert_eq!(anchored(Anchor::BottomRight), Coordinates::new(7, 16));
+    }
+
+    #[test]
+    fn the_pattern_can_be_placed_at_an_explicit_offset() {
+        assert_eq!(
+            position(Placement::Offset(Coordinates::new(5, 6))),
+            Coordinates::new(5, 6)
+        );
+    }
+
+    #[test]
+    fn it_should_fail_when_the_pattern_does_not_fit_at_the_offset() {
+        assert_eq!(
+            Placement::Offset(Coordinates::new(8, 0))
+                .position(&Size::new(10, 20), &Size::new(3, 4)),
+            Err(PatternDoesNotFitError {
+                pattern_rows: 3,
+                pattern_columns: 4,
+                grid_rows: 10,
+                grid_columns: 20,
+                position: Coordinates::new(8, 0),
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_fail_when_the_offset_is_too_big_to_add_the_pattern_size() {
+        assert!(Placement::Offset(Coordinates::new(usize::MAX, 0))
+            .position(&Size::new(10, 20), &Size::new(3, 4))
+            .is_err());
+    }
+
+    #[test]
+    fn it_should_fail_when_the_pattern_is_bigger_than_the_grid() {
+        assert!(Placement::default()
+            .position(&Size::new(2, 20), &Size::new(3, 4))
+            .is_err());
+    }
+
+    #[test]
+    fn an_anchor_should_be_parsed_from_its_name() {
+        assert_eq!("bottom-right".parse(), Ok(Anchor::BottomRight));
+        assert_eq!(Anchor::TopLeft.to_string(), "top-left");
+        assert_eq!(
+            "middle".parse::<Anchor>(),
+            Err(ParseAnchorError {
+                invalid_anchor: "middle".to_owned()
+            })
+        );
+    }
+}
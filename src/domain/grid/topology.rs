@@ -0,0 +1,253 @@
+use std::{error::Error, fmt, str::FromStr};
+
+use crate::domain::cell::coordinates::Coordinates;
+
+use super::size::Size;
+
+/// How the grid edges are stitched together.
+///
+/// It decides which cell is the neighbour of a cell on the grid edge.
+/// When two edges are stitched "with a twist" the cell on the other edge
+/// is the mirrored one. For example, on a Klein bottle the top neighbour
+/// of the cell at row 0 and column 0 is the cell at the last row and the
+/// last column.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Topology {
+    /// Bounded plane. Cells outside the grid are always dead.
+    Plane,
+    /// Top and bottom edges and left and right edges are stitched together.
+    #[default]
+    Torus,
+    /// Left and right edges are stitched together.
+    HorizontalCylinder,
+    /// Top and bottom edges are stitched together.
+    VerticalCylinder,
+    /// Left and right edges are stitched together.
+    /// Top and bottom edges are stitched together with a twist.
+    KleinBottle,
+    /// Top and bottom edges and left and right edges are stitched together with a twist.
+    CrossSurface,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseTopologyError {
+    pub invalid_topology: String,
+}
+
+impl Error for ParseTopologyError {}
+
+impl fmt::Display for ParseTopologyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid topology '{}'", self.invalid_topology)
+    }
+}
+
+const TOPOLOGIES: [(Topology, &str); 6] = [
+    (Topology::Plane, "plane"),
+    (Topology::Torus, "torus"),
+    (Topology::HorizontalCylinder, "horizontal-cylinder"),
+    (Topology::VerticalCylinder, "vertical-cylinder"),
+    (Topology::KleinBottle, "klein-bottle"),
+    (Topology::CrossSurface, "cross-surface"),
+];
+
+impl FromStr for Topology {
+    type Err = ParseTopologyError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        TOPOLOGIES
+            .iter()
+            .find(|(_, name)| name.eq_ignore_ascii_case(text.trim()))
+            .map(|(topology, _)| *topology)
+            .ok_or_else(|| ParseTopologyError {
+                invalid_topology: text.to_owned(),
+            })
+    }
+}
+
+impl fmt::Display for Topology {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (_, name) = TOPOLOGIES
+            .iter()
+            .find(|(topology, _)| topology == self)
+            .expect("all topologies should have a name");
+        write!(f, "{name}")
+    }
+}
+
+impl Topology {
+    /// It returns the coordinates inside a grid with the given size for
+    /// some coordinates which can be outside the grid, or `None` if the
+    /// coordinates are outside the grid and the edges are not stitched.
+    #[must_use]
+    pub fn resolve(self, row: i64, column: i64, grid_size: &Size) -> Option<Coordinates> {
+        let rows = i64::try_from(grid_size.rows).ok()?;
+        let columns = i64::try_from(grid_size.columns).ok()?;
+
+        let (mut row, mut column) = (row, column);
+
+        if !(0..rows).contains(&row) {
+            if !self.stitches_top_and_bottom() {
+                return None;
+            }
+            row = row.rem_euclid(rows);
+            if self.twists_top_and_bottom() {
+                column = columns - 1 - column;
+            }
+        }
+
+        if !(0..columns).contains(&column) {
+            if !self.stitches_left_and_right() {
+                return None;
+            }
+            column = column.rem_euclid(columns);
+            if self.twists_left_and_right() {
+                row = rows - 1 - row;
+            }
+        }
+
+        Some(Coordinates::new(
+            usize::try_from(row).ok()?,
+            usize::try_from(column).ok()?,
+        ))
+    }
+
+    fn stitches_top_and_bottom(self) -> bool {
+        matches!(
+            self,
+            Topology::Torus
+                | Topology::VerticalCylinder
+                | Topology::KleinBottle
+                | Topology::CrossSurface
+        )
+    }
+
+    fn stitches_left_and_right(self) -> bool {
+        matches!(
+            self,
+            Topology::Torus
+                | Topology::HorizontalCylinder
+                | Topology::KleinBottle
+                | Topology::CrossSurface
+        )
+    }
+
+    fn twists_top_and_bottom(self) -> bool {
+        matches!(self, Topology::KleinBottle | Topology::CrossSurface)
+    }
+
+    fn twists_left_and_right(self) -> bool {
+        matches!(self, Topology::CrossSurface)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::{
+        cell::coordinates::Coordinates,
+        grid::{
+            size::Size,
+            topology::{ParseTopologyError, Topology},
+        },
+    };
+
+    #[test]
+    fn the_default_topology_is_the_torus() {
+        assert_eq!(Topology::default(), Topology::Torus);
+    }
+
+    #[test]
+    fn coordinates_inside_the_grid_are_not_changed() {
+        assert_eq!(
+            Topology::Plane.resolve(1, 2, &Size::new(3, 3)),
+            Some(Coordinates::new(1, 2))
+        );
+    }
+
+    #[test]
+    fn coordinates_outside_a_plane_do_not_exist() {
+        assert_eq!(Topology::Plane.resolve(-1, 0, &Size::new(3, 3)), None);
+        assert_eq!(Topology::Plane.resolve(0, 3, &Size::new(3, 3)), None);
+    }
+
+    #[test]
+    fn coordinates_outside_a_torus_wrap_around_both_edges() {
+        assert_eq!(
+            Topology::Torus.resolve(-1, 3, &Size::new(3, 3)),
+            Some(Coordinates::new(2, 0))
+        );
+    }
+
+    #[test]
+    fn coordinates_outside_a_horizontal_cylinder_only_wrap_around_left_and_right_edges() {
+        let size = Size::new(3, 3);
+
+        assert_eq!(
+            Topology::HorizontalCylinder.resolve(0, -1, &size),
+            Some(Coordinates::new(0, 2))
+        );
+        assert_eq!(Topology::HorizontalCylinder.resolve(-1, 0, &size), None);
+    }
+
+    #[test]
+    fn coordinates_outside_a_vertical_cylinder_only_wrap_around_top_and_bottom_edges() {
+        let size = Size::new(3, 3);
+
+        assert_eq!(
+            Topology::VerticalCylinder.resolve(-1, 0, &size),
+            Some(Coordinates::new(2, 0))
+        );
+        assert_eq!(Topology::VerticalCylinder.resolve(0, -1, &size), None);
+    }
+
+    #[test]
+    fn coordinates_outside_a_klein_bottle_wrap_around_top_and_bottom_edges_with_a_twist() {
+        let size = Size::new(3, 3);
+
+        assert_eq!(
+            Topology::KleinBottle.resolve(-1, 0, &size),
+            Some(Coordinates::new(2, 2))
+        );
+        assert_eq!(
+            Topology::KleinBottle.resolve(1, -1, &size),
+            Some(Coordinates::new(1, 2))
+        );
+    }
+
+    #[test]
+    fn coordinates_outside_a_cross_surface_wrap_around_both_edges_with_a_twist() {
+        let size = Size::new(3, 3);
+
+        assert_eq!(
+            Topology::CrossSurface.resolve(-1, 0, &size),
+            Some(Coordinates::new(2, 2))
+        );
+        assert_eq!(
+            Topology::CrossSurface.resolve(0, -1, &size),
+            Some(Coordinates::new(2, 2))
+        );
+    }
+
+    #[test]
+    fn it_should_be_parsed_from_its_name() {
+        assert_eq!("plane".parse::<Topology>().unwrap(), Topology::Plane);
+        assert_eq!(
+            "Klein-Bottle".parse::<Topology>().unwrap(),
+            Topology::KleinBottle
+        );
+        assert_eq!(
+            "sphere".parse::<Topology>().unwrap_err(),
+            ParseTopologyError {
+                invalid_topology: "sphere".to_owned()
+            }
+        );
+    }
+
+    #[test]
+    fn it_should_be_displayed_with_its_name() {
+        assert_eq!(
+            Topology::HorizontalCylinder.to_string(),
+            "horizontal-cylinder"
+        );
+    }
+}
@@ -0,0 +1,327 @@
+pub mod node;
+
+use crate::domain::{
+    grid::{size::Size, Grid},
+    rule::{Rule, UnboundedB0RuleError},
+    universe::{position::Position, Universe},
+};
+
+use self::node::{NodeId, NodeStore};
+
+/// Biggest quadtree level. The universe can not be wider than `2^MAX_LEVEL` cells.
+const MAX_LEVEL: u8 = 60;
+
+/// `HashLife` engine.
+///
+/// The universe is stored in a memoized quadtree, so patterns with a lot
+/// of repetition in space and time can be advanced by huge numbers of
+/// generations. For example, jumping `2^k` generations only takes a few
+/// more steps than jumping `2^(k-1)` generations.
+///
+/// Like the `Universe`, it does not have edges, and it can not be built
+/// with rules where a dead cell with no live neighbours is born (`B0`).
+pub struct HashLife {
+    store: NodeStore,
+    root: NodeId,
+    /// Position of the root node left top corner
+    origin: Position,
+    generation: u128,
+}
+
+impl HashLife {
+    /// # Errors
+    ///
+    /// Will return `Err` if dead cells with no live neighbours are born
+    /// with the rule (`B0`).
+    pub fn new(rule: Rule) -> Result<Self, UnboundedB0RuleError> {
+        rule.check_unbounded()?;
+
+        let mut store = NodeStore::new(rule);
+        let root = store.empty(3);
+
+        Ok(Self {
+            store,
+            root,
+            origin: Position::new(0, 0),
+            generation: 0,
+        })
+    }
+
+    /// It places the grid left top corner at position (0,0).
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` like `new`.
+    pub fn from_grid(grid: &Grid, rule: Rule) -> Result<Self, UnboundedB0RuleError> {
+        Self::from_live_cells(
+            grid.iter()
+                .filter(|coordinates| grid.get_cell(coordinates).is_live())
+                .map(|coordinates| Position::from(&coordinates)),
+            rule,
+        )
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` like `new`.
+    pub fn from_universe(universe: &Universe, rule: Rule) -> Result<Self, UnboundedB0RuleError> {
+        Self::from_live_cells(universe.live_cells().copied(), rule)
+    }
+
+    fn from_live_cells<I: IntoIterator<Item = Position>>(
+        live_cells: I,
+        rule: Rule,
+    ) -> Result<Self, UnboundedB0RuleError> {
+        let mut hashlife = Self::new(rule)?;
+        for position in live_cells {
+            hashlife.set_cell(&position, true);
+        }
+        Ok(hashlife)
+    }
+
+    #[must_use]
+    pub fn rule(&self) -> &Rule {
+        self.store.rule()
+    }
+
+    /// Number of generations advanced so far.
+    #[must_use]
+    pub fn generation(&self) -> u128 {
+        self.generation
+    }
+
+    /// Number of live cells.
+    #[must_use]
+    pub fn population(&self) -> u64 {
+        self.store.population(self.root)
+    }
+
+    #[must_use]
+    pub fn is_live(&self, position: &Position) -> bool {
+        let side = self.side();
+        let row = position.row - self.origin.row;
+        let column = position.column - self.origin.column;
+
+        if !(0..side).contains(&row) || !(0..side).contains(&column) {
+            return false;
+        }
+
+        // Both are inside the root node, so they are not negative
+        self.store
+            .cell(self.root, row.cast_unsigned(), column.cast_unsigned())
+    }
+
+    /// # Panics
+    ///
+    /// Will panic if the universe would need to be wider than `2^60` cells.
+    pub fn set_cell(&mut self, position: &Position, live: bool) {
+        while !self.contains(position) {
+            self.expand();
+        }
+
+        // The root node contains the position, so both are not negative
+        let row = (position.row - self.origin.row).cast_unsigned();
+        let column = (position.column - self.origin.column).cast_unsigned();
+
+        self.root = self.store.set_cell(self.root, row, column, live);
+    }
+
+    /// Jump `2^k` generations.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the universe would need to be wider than `2^60` cells.
+    pub fn step_pow2(&mut self, k: u8) {
+        // The pattern must be in the center quarter of the root node,
+        // so that it can not grow outside the result.
+        while self.store.level(self.root) < k + 2 || !self.is_padded() {
+            self.expand();
+        }
+        self.expand();
+
+        let half_of_the_result = 1i64 << (self.store.level(self.root) - 2);
+
+        self.root = self.store.successor(self.root, k);
+        self.origin = self
+            .origin
+            .translate(half_of_the_result, half_of_the_result);
+        self.generation += 1 << k;
+    }
+
+    /// Advance the given number of generations.
+    pub fn step(&mut self, generations: u64) {
+        // One jump for every bit of the 64 bits of the generations
+        for k in 0..64 {
+            if generations & (1 << k) != 0 {
+                self.step_pow2(k);
+            }
+        }
+    }
+
+    /// It returns a window into the universe with the given size and the
+    /// left top corner at the given position.
+    #[must_use]
+    pub fn viewport(&self, origin: &Position, size: &Size) -> Grid {
+        self.to_universe().viewport(origin, size)
+    }
+
+    #[must_use]
+    pub fn to_universe(&self) -> Universe {
+        let mut cells = vec![];
+        self.store.live_cells(self.root, 0, 0, &mut cells);
+
+        Universe::with_live_cells(
+            cells
+                .into_iter()
+                // The universe is at most 2^60 cells wide, so they fit in an i64
+                .map(|(row, column)| {
+                    self.origin
+                        .translate(row.cast_signed(), column.cast_signed())
+                }),
+        )
+    }
+
+    /// It returns the grid with the given size and the left top corner at position (0,0).
+    #[must_use]
+    pub fn to_grid(&self, size: &Size) -> Grid {
+        self.viewport(&Position::new(0, 0), size)
+    }
+
+    fn side(&self) -> i64 {
+        1i64 << self.store.level(self.root)
+    }
+
+    fn contains(&self, position: &Position) -> bool {
+        let side = self.side();
+        (0..side).contains(&(position.row - self.origin.row))
+            && (0..side).contains(&(position.column - self.origin.column))
+    }
+
+    fn expand(&mut self) {
+        let level = self.store.level(self.root);
+        assert!(level < MAX_LEVEL, "The universe is too big");
+
+        let quarter = 1i64 << (level - 1);
+
+        self.root = self.store.expand(self.root);
+        self.origin = self.origin.translate(-quarter, -quarter);
+    }
+
+    /// True if all the live cells are in the center quarter of the root node.
+    fn is_padded(&mut self) -> bool {
+        let center = self.store.center(self.root);
+        let center = self.store.center(center);
+        self.store.population(center) == self.population()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::domain::{
+        fixtures::glider,
+        grid::{functions::next_generation::next_generation, size::Size, topology::Topology, Grid},
+        hashlife::HashLife,
+        rule::{Rule, UnboundedB0RuleError},
+        universe::{position::Position, Universe},
+    };
+
+    fn r_pentomino_on_a_plane() -> Grid {
+        let mut rows = vec!["⬛".repeat(40); 40];
+        rows[19] = format!("{}⬛⬜⬜{}", "⬛".repeat(18), "⬛".repeat(19));
+        rows[20] = format!("{}⬜⬜⬛{}", "⬛".repeat(18), "⬛".repeat(19));
+        rows[21] = format!("{}⬛⬜⬛{}", "⬛".repeat(18), "⬛".repeat(19));
+
+        Grid::from_str(&rows.join("\n"))
+            .unwrap()
+            .with_topology(Topology::Plane)
+    }
+
+    #[test]
+    fn it_should_be_converted_from_and_to_a_grid() {
+        let grid = glider().with_topology(Topology::Plane);
+
+        let hashlife = HashLife::from_grid(&grid, Rule::default()).unwrap();
+
+        assert_eq!(hashlife.population(), 5);
+        assert_eq!(hashlife.to_grid(&grid.size()), grid);
+    }
+
+    #[test]
+    fn it_should_be_converted_from_and_to_an_unbounded_universe() {
+        let universe = Universe::with_live_cells([Position::new(-5, 7), Position::new(3, -2)]);
+
+        let hashlife = HashLife::from_universe(&universe, Rule::default()).unwrap();
+
+        assert_eq!(hashlife.to_universe(), universe);
+    }
+
+    #[test]
+    fn it_should_advance_like_next_generation_on_a_plane() {
+        let mut grid = r_pentomino_on_a_plane();
+        let mut hashlife = HashLife::from_grid(&grid, Rule::default()).unwrap();
+
+        for _ in 0..20 {
+            grid = next_generation(&grid, &Rule::default());
+            hashlife.step(1);
+
+            assert_eq!(hashlife.to_grid(&grid.size()), grid);
+        }
+    }
+
+    #[test]
+    fn it_should_jump_a_power_of_two_generations() {
+        let mut grid = r_pentomino_on_a_plane();
+        let mut hashlife = HashLife::from_grid(&grid, Rule::default()).unwrap();
+
+        for _ in 0..16 {
+            grid = next_generation(&grid, &Rule::default());
+        }
+        hashlife.step_pow2(4);
+
+        assert_eq!(hashlife.generation(), 16);
+        assert_eq!(hashlife.to_grid(&grid.size()), grid);
+    }
+
+    #[test]
+    fn it_should_apply_the_given_rule() {
+        let high_life: Rule = "B36/S23".parse().unwrap();
+
+        let mut grid = r_pentomino_on_a_plane();
+        let mut hashlife = HashLife::from_grid(&grid, high_life.clone()).unwrap();
+
+        for _ in 0..8 {
+            grid = next_generation(&grid, &high_life);
+        }
+        hashlife.step(8);
+
+        assert_eq!(hashlife.to_grid(&grid.size()), grid);
+    }
+
+    #[test]
+    fn it_should_advance_a_huge_number_of_generations() {
+        // After four generations a glider moves one cell down and one cell right
+        let mut hashlife = HashLife::from_grid(&glider(), Rule::default()).unwrap();
+
+        hashlife.step_pow2(40);
+
+        let offset = 1i64 << 38;
+        assert_eq!(hashlife.generation(), 1 << 40);
+        assert_eq!(hashlife.population(), 5);
+        assert_eq!(
+            hashlife.viewport(&Position::new(offset, offset), &Size::new(3, 3)),
+            glider().with_topology(Topology::Plane)
+        );
+    }
+
+    #[test]
+    fn rules_with_births_from_no_live_neighbours_are_not_supported() {
+        let b0_rule: Rule = "B03/S23".parse().unwrap();
+
+        assert_eq!(
+            HashLife::new(b0_rule.clone()).err(),
+            Some(UnboundedB0RuleError { rule: b0_rule })
+        );
+    }
+}
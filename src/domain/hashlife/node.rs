@@ -0,0 +1,331 @@
+use std::collections::HashMap;
+
+use crate::domain::{cell::state::State, rule::Rule};
+
+pub type NodeId = usize;
+
+pub const DEAD_LEAF: NodeId = 0;
+pub const LIVE_LEAF: NodeId = 1;
+
+/// Quadtree node.
+///
+/// A node of level `n` is a square of `2^n` x `2^n` cells. Level 0 nodes
+/// are single cells. Any other node is made of four nodes of the level
+/// below: north-west, north-east, south-west and south-east.
+#[derive(Debug, Clone, Copy)]
+pub struct Node {
+    pub level: u8,
+    pub nw: NodeId,
+    pub ne: NodeId,
+    pub sw: NodeId,
+    pub se: NodeId,
+    pub population: u64,
+}
+
+/// Canonical store of quadtree nodes.
+///
+/// Identical nodes are only stored once (hash-consing), so two nodes are
+/// equal if and only if they have the same id. That makes it possible to
+/// memoize the result of advancing a node.
+pub struct NodeStore {
+    nodes: Vec<Node>,
+    index: HashMap<[NodeId; 4], NodeId>,
+    results: HashMap<(NodeId, u8), NodeId>,
+    empty_nodes: Vec<NodeId>,
+    rule: Rule,
+}
+
+impl NodeStore {
+    #[must_use]
+    pub fn new(rule: Rule) -> Self {
+        let leaf = |population| Node {
+            level: 0,
+            nw: DEAD_LEAF,
+            ne: DEAD_LEAF,
+            sw: DEAD_LEAF,
+            se: DEAD_LEAF,
+            population,
+        };
+
+        Self {
+            nodes: vec![leaf(0), leaf(1)],
+            index: HashMap::new(),
+            results: HashMap::new(),
+            empty_nodes: vec![DEAD_LEAF],
+            rule,
+        }
+    }
+
+    #[must_use]
+    pub fn rule(&self) -> &Rule {
+        &self.rule
+    }
+
+    #[must_use]
+    pub fn get(&self, id: NodeId) -> &Node {
+        &self.nodes[id]
+    }
+
+    #[must_use]
+    pub fn level(&self, id: NodeId) -> u8 {
+        self.nodes[id].level
+    }
+
+    #[must_use]
+    pub fn population(&self, id: NodeId) -> u64 {
+        self.nodes[id].population
+    }
+
+    /// It returns the canonical node made of the four given nodes.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the four nodes do not have the same level.
+    pub fn join(&mut self, nw: NodeId, ne: NodeId, sw: NodeId, se: NodeId) -> NodeId {
+        if let Some(id) = self.index.get(&[nw, ne, sw, se]) {
+            return *id;
+        }
+
+        let level = self.level(nw);
+        assert!(
+            self.level(ne) == level && self.level(sw) == level && self.level(se) == level,
+            "Quadtree children should have the same level"
+        );
+
+        let node = Node {
+            level: level + 1,
+            nw,
+            ne,
+            sw,
+            se,
+            population: self.population(nw)
+                + self.population(ne)
+                + self.population(sw)
+                + self.population(se),
+        };
+
+        let id = self.nodes.len();
+        self.nodes.push(node);
+        self.index.insert([nw, ne, sw, se], id);
+        id
+    }
+
+    /// Node of the given level with only dead cells.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the store has no empty leaf, which it gets when created.
+    pub fn empty(&mut self, level: u8) -> NodeId {
+        while self.empty_nodes.len() <= level as usize {
+            let below = *self
+                .empty_nodes
+                .last()
+                .expect("there is always an empty leaf");
+            let id = self.join(below, below, below, below);
+            self.empty_nodes.push(id);
+        }
+        self.empty_nodes[level as usize]
+    }
+
+    /// It returns a node one level up with the given node in its center.
+    pub fn expand(&mut self, id: NodeId) -> NodeId {
+        let node = *self.get(id);
+        let border = self.empty(node.level - 1);
+
+        let nw = self.join(border, border, border, node.nw);
+        let ne = self.join(border, border, node.ne, border);
+        let sw = self.join(border, node.sw, border, border);
+        let se = self.join(node.se, border, border, border);
+
+        self.join(nw, ne, sw, se)
+    }
+
+    /// Center node one level down.
+    pub fn center(&mut self, id: NodeId) -> NodeId {
+        let node = *self.get(id);
+        let (nw, ne, sw, se) = (
+            *self.get(node.nw),
+            *self.get(node.ne),
+            *self.get(node.sw),
+            *self.get(node.se),
+        );
+        self.join(nw.se, ne.sw, sw.ne, se.nw)
+    }
+
+    /// It returns the center node one level down advanced `2^step` generations.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the node level is lower than 2 or lower than `step + 2`.
+    pub fn successor(&mut self, id: NodeId, step: u8) -> NodeId {
+        let level = self.level(id);
+        assert!(
+            level >= 2 && step <= level - 2,
+            "A node of level {level} can not be advanced 2^{step} generations"
+        );
+
+        if let Some(result) = self.results.get(&(id, step)) {
+            return *result;
+        }
+
+        let result = if self.population(id) == 0 {
+            self.empty(level - 1)
+        } else if level == 2 {
+            self.successor_of_4x4(id)
+        } else {
+            self.successor_of_branch(id, step)
+        };
+
+        self.results.insert((id, step), result);
+        result
+    }
+
+    fn successor_of_branch(&mut self, id: NodeId, step: u8) -> NodeId {
+        let level = self.level(id);
+        let node = *self.get(id);
+        let (nw, ne, sw, se) = (
+            *self.get(node.nw),
+            *self.get(node.ne),
+            *self.get(node.sw),
+            *self.get(node.se),
+        );
+
+        // Nine overlapping sub-nodes one level down covering the node
+        let n00 = node.nw;
+        let n01 = self.join(nw.ne, ne.nw, nw.se, ne.sw);
+        let n02 = node.ne;
+        let n10 = self.join(nw.sw, nw.se, sw.nw, sw.ne);
+        let n11 = self.join(nw.se, ne.sw, sw.ne, se.nw);
+        let n12 = self.join(ne.sw, ne.se, se.nw, se.ne);
+        let n20 = node.sw;
+        let n21 = self.join(sw.ne, se.nw, sw.se, se.sw);
+        let n22 = node.se;
+
+        let sub_step = step.min(level - 3);
+
+        let c00 = self.successor(n00, sub_step);
+        let c01 = self.successor(n01, sub_step);
+        let c02 = self.successor(n02, sub_step);
+        let c10 = self.successor(n10, sub_step);
+        let c11 = self.successor(n11, sub_step);
+        let c12 = self.successor(n12, sub_step);
+        let c20 = self.successor(n20, sub_step);
+        let c21 = self.successor(n21, sub_step);
+        let c22 = self.successor(n22, sub_step);
+
+        let quarters = [
+            self.join(c00, c01, c10, c11),
+            self.join(c01, c02, c11, c12),
+            self.join(c10, c11, c20, c21),
+            self.join(c11, c12, c21, c22),
+        ];
+
+        let [nw, ne, sw, se] = quarters.map(|quarter| {
+            if step == level - 2 {
+                // Second half of the generations
+                self.successor(quarter, sub_step)
+            } else {
+                // All the generations were already calculated
+                self.center(quarter)
+            }
+        });
+
+        self.join(nw, ne, sw, se)
+    }
+
+    /// Base case: center 2x2 cells of a 4x4 node after one generation.
+    fn successor_of_4x4(&mut self, id: NodeId) -> NodeId {
+        let mut cells = [[false; 4]; 4];
+        for (row, cells_row) in cells.iter_mut().enumerate() {
+            for (column, cell) in cells_row.iter_mut().enumerate() {
+                *cell = self.cell(id, row as u64, column as u64);
+            }
+        }
+
+        let mut next = [DEAD_LEAF; 4];
+        for (index, (row, column)) in [(1, 1), (1, 2), (2, 1), (2, 2)].into_iter().enumerate() {
+            let number_of_live_neighbors = cells[row - 1..=row + 1]
+                .iter()
+                .flat_map(|cells_row| &cells_row[column - 1..=column + 1])
+                .filter(|live| **live)
+                .count()
+                - usize::from(cells[row][column]);
+            let state = if cells[row][column] {
+                State::Live
+            } else {
+                State::Dead
+            };
+            if self.rule.next_state(&state, number_of_live_neighbors) == State::Live {
+                next[index] = LIVE_LEAF;
+            }
+        }
+
+        self.join(next[0], next[1], next[2], next[3])
+    }
+
+    /// State of the cell at the given row and column relative to the node left top corner.
+    #[must_use]
+    pub fn cell(&self, id: NodeId, row: u64, column: u64) -> bool {
+        let node = self.get(id);
+        if node.level == 0 {
+            return id == LIVE_LEAF;
+        }
+
+        let half = 1u64 << (node.level - 1);
+        let child = match (row < half, column < half) {
+            (true, true) => node.nw,
+            (true, false) => node.ne,
+            (false, true) => node.sw,
+            (false, false) => node.se,
+        };
+        self.cell(child, row % half, column % half)
+    }
+
+    /// It returns the same node with the cell at the given row and column set.
+    pub fn set_cell(&mut self, id: NodeId, row: u64, column: u64, live: bool) -> NodeId {
+        let node = *self.get(id);
+        if node.level == 0 {
+            return if live { LIVE_LEAF } else { DEAD_LEAF };
+        }
+
+        let half = 1u64 << (node.level - 1);
+        let (row_in_child, column_in_child) = (row % half, column % half);
+
+        match (row < half, column < half) {
+            (true, true) => {
+                let nw = self.set_cell(node.nw, row_in_child, column_in_child, live);
+                self.join(nw, node.ne, node.sw, node.se)
+            }
+            (true, false) => {
+                let ne = self.set_cell(node.ne, row_in_child, column_in_child, live);
+                self.join(node.nw, ne, node.sw, node.se)
+            }
+            (false, true) => {
+                let sw = self.set_cell(node.sw, row_in_child, column_in_child, live);
+                self.join(node.nw, node.ne, sw, node.se)
+            }
+            (false, false) => {
+                let se = self.set_cell(node.se, row_in_child, column_in_child, live);
+                self.join(node.nw, node.ne, node.sw, se)
+            }
+        }
+    }
+
+    /// Positions of the live cells relative to the node left top corner.
+    pub fn live_cells(&self, id: NodeId, row: u64, column: u64, cells: &mut Vec<(u64, u64)>) {
+        let node = self.get(id);
+        if node.population == 0 {
+            return;
+        }
+        if node.level == 0 {
+            cells.push((row, column));
+            return;
+        }
+
+        let half = 1u64 << (node.level - 1);
+        self.live_cells(node.nw, row, column, cells);
+        self.live_cells(node.ne, row, column + half, cells);
+        self.live_cells(node.sw, row + half, column, cells);
+        self.live_cells(node.se, row + half, column + half, cells);
+    }
+}
@@ -0,0 +1,100 @@
+/// A key the player pressed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Char(char),
+    Up,
+    Down,
+    Left,
+    Right,
+    Enter,
+    Escape,
+    /// Ctrl-C, which does not stop the process while the terminal is
+    /// reading the keys.
+    Interrupt,
+}
+
+/// What the player can do while the game is running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control {
+    /// Pause the game or resume it if it is paused.
+    PauseOrResume,
+    /// Show the next generation while the game is paused.
+    Step,
+    /// Halve the generation lifetime.
+    SpeedUp,
+    /// Double the generation lifetime.
+    SlowDown,
+    /// Start again from the initial pattern.
+    Restart,
+    Quit,
+}
+
+const KEYS: [(char, Control); 6] = [
+    (' ', Control::PauseOrResume),
+    ('n', Control::Step),
+    ('+', Control::SpeedUp),
+    ('-', Control::SlowDown),
+    ('r', Control::Restart),
+    ('q', Control::Quit),
+];
+
+impl Control {
+    /// Control for the given key, if the key has one.
+    #[must_use]
+    pub fn for_key(key: Key) -> Option<Self> {
+        match key {
+            Key::Char(key) => KEYS
+                .iter()
+                .find(|(control_key, _)| *control_key == key.to_ascii_lowercase())
+                .map(|(_, control)| *control),
+            Key::Escape | Key::Interrupt => Some(Control::Quit),
+            _ => None,
+        }
+    }
+}
+
+pub trait Keyboard {
+    /// Next key pressed, if any. It does not wait for the player to press
+    /// a key.
+    fn read_key(&self) -> Option<Key>;
+
+    /// It waits until the player presses a key. It returns `None` if no
+    /// more keys can be read.
+    fn wait_for_key(&self) -> Option<Key>;
+
+    /// Control for the next key pressed with a control, if any. Other keys
+    /// are ignored.
+    fn read_control(&self) -> Option<Control> {
+        while let Some(key) = self.read_key() {
+            if let Some(control) = Control::for_key(key) {
+                return Some(control);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::input::keyboard::{Control, Key};
+
+    #[test]
+    fn every_control_should_have_a_key() {
+        assert_eq!(
+            Control::for_key(Key::Char(' ')),
+            Some(Control::PauseOrResume)
+        );
+        assert_eq!(Control::for_key(Key::Char('n')), Some(Control::Step));
+        assert_eq!(Control::for_key(Key::Char('+')), Some(Control::SpeedUp));
+        assert_eq!(Control::for_key(Key::Char('-')), Some(Control::SlowDown));
+        assert_eq!(Control::for_key(Key::Char('r')), Some(Control::Restart));
+        assert_eq!(Control::for_key(Key::Char('Q')), Some(Control::Quit));
+        assert_eq!(Control::for_key(Key::Interrupt), Some(Control::Quit));
+    }
+
+    #[test]
+    fn other_keys_should_be_ignored() {
+        assert_eq!(Control::for_key(Key::Char('x')), None);
+        assert_eq!(Control::for_key(Key::Up), None);
+    }
+}
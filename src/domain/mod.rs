@@ -1,6 +1,18 @@
+pub mod analysis;
 pub mod cell;
+#[cfg(test)]
+pub(crate) mod fixtures;
 pub mod game;
 pub mod grid;
+pub mod hashlife;
+pub mod input;
+pub mod outcome;
 pub mod output;
+pub mod pattern;
+pub mod rule;
 pub mod settings;
+pub mod simulation;
+pub mod stability;
+pub mod statistics;
 pub mod timer;
+pub mod universe;
@@ -0,0 +1,59 @@
+use std::time::Duration;
+
+use super::{grid::Grid, stability::StopReason, statistics::GenerationStats};
+
+/// Result of a simulation.
+///
+/// Rendering the final grid as text is only one way to show it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationOutcome {
+    /// Last generation shown. On an unbounded universe, it is the part of
+    /// the universe inside the viewport.
+    pub final_grid: Grid,
+    /// Number of generations shown, including the initial pattern.
+    pub generations: u32,
+    /// Number of live cells in every generation shown.
+    pub population_history: Vec<usize>,
+    /// Metrics of every generation shown, if they were collected.
+    pub statistics: Vec<GenerationStats>,
+    pub stop_reason: StopReason,
+    /// Time spent running the simulation, including the waits between
+    /// generations.
+    pub elapsed: Duration,
+}
+
+impl SimulationOutcome {
+    /// Number of the last generation shown, the pattern is generation 0.
+    #[must_use]
+    pub fn last_generation(&self) -> u32 {
+        self.generations.saturating_sub(1)
+    }
+
+    /// Number of live cells in the last generation shown.
+    #[must_use]
+    pub fn final_population(&self) -> usize {
+        self.population_history.last().copied().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr, time::Duration};
+
+    use crate::domain::{grid::Grid, outcome::SimulationOutcome, stability::StopReason};
+
+    #[test]
+    fn it_should_tell_the_last_generation_and_its_population() {
+        let outcome = SimulationOutcome {
+            final_grid: Grid::from_str("⬜⬜⬜").unwrap(),
+            generations: 3,
+            population_history: vec![4, 5, 3],
+            statistics: vec![],
+            stop_reason: StopReason::GenerationLimit,
+            elapsed: Duration::ZERO,
+        };
+
+        assert_eq!(outcome.last_generation(), 2);
+        assert_eq!(outcome.final_population(), 3);
+    }
+}
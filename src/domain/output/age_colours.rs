@@ -0,0 +1,109 @@
+use std::fmt::Write;
+
+use crate::domain::{
+    cell::coordinates::Coordinates,
+    grid::ages::{Age, CellAges},
+};
+
+/// ANSI 256 colours for the live cells, from newborn to old. Cells older
+/// than the palette use the last colour.
+const AGE_COLOURS: [u8; 12] = [231, 226, 190, 154, 118, 82, 46, 43, 39, 33, 27, 21];
+
+/// ANSI 256 colours for the cells that died in the last generations, from
+/// the most recent one.
+const TRAIL_COLOURS: [u8; 3] = [240, 237, 234];
+
+/// It renders the grid painting the background of every live cell with a
+/// colour for its age, so new activity and stable debris look different.
+/// With the trail, the cells that died recently are painted dimmed.
+///
+/// Every cell is drawn as `cell_width` spaces.
+#[must_use]
+pub fn render_by_age(ages: &CellAges, cell_width: usize, trail: bool) -> String {
+    let mut output = String::new();
+    let cell = " ".repeat(cell_width);
+
+    for row in 0..ages.rows() {
+        let mut current_colour = None;
+
+        for column in 0..ages.columns() {
+            let colour = colour_for(ages.age(&Coordinates::new(row, column)), trail);
+
+            if colour != current_colour {
+                match colour {
+                    Some(colour) => {
+                        let _ = write!(output, "\x1B[48;5;{colour}m");
+                    }
+                    None => output.push_str("\x1B[0m"),
+                }
+                current_colour = colour;
+            }
+            output.push_str(&cell);
+        }
+
+        if current_colour.is_some() {
+            output.push_str("\x1B[0m");
+        }
+        output.push('\n');
+    }
+
+    output
+}
+
+fn colour_for(age: Age, trail: bool) -> Option<u8> {
+    let colour = |palette: &[u8], generations: u32| {
+        let index = usize::try_from(generations - 1).unwrap_or(usize::MAX);
+        palette.get(index).or(palette.last()).copied()
+    };
+
+    match age {
+        Age::Live(generations) => colour(&AGE_COLOURS, generations),
+        Age::Dead(generations) if trail && generations as usize <= TRAIL_COLOURS.len() => {
+            colour(&TRAIL_COLOURS, generations)
+        }
+        Age::Dead(_) | Age::Unborn => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::domain::{
+        grid::{ages::CellAges, Grid},
+        output::age_colours::render_by_age,
+    };
+
+    fn ages_after_dying() -> CellAges {
+        let mut ages = CellAges::new(&Grid::from_str("⬜⬜⬛").unwrap());
+        ages.update(&Grid::from_str("⬜⬛⬛").unwrap());
+        ages
+    }
+
+    #[test]
+    fn live_cells_should_be_painted_with_the_colour_for_their_age() {
+        let mut ages = CellAges::new(&Grid::from_str("⬜⬛").unwrap());
+        ages.update(&Grid::from_str("⬜⬜").unwrap());
+
+        assert_eq!(
+            render_by_age(&ages, 2, false),
+            "\x1B[48;5;226m  \x1B[48;5;231m  \x1B[0m\n"
+        );
+    }
+
+    #[test]
+    fn dead_cells_should_not_be_painted_without_the_trail() {
+        assert_eq!(
+            render_by_age(&ages_after_dying(), 1, false),
+            "\x1B[48;5;226m \x1B[0m  \n"
+        );
+    }
+
+    #[test]
+    fn recently_dead_cells_should_be_dimmed_with_the_trail() {
+        assert_eq!(
+            render_by_age(&ages_after_dying(), 1, true),
+            "\x1B[48;5;226m \x1B[48;5;240m \x1B[0m \n"
+        );
+    }
+}
@@ -0,0 +1,95 @@
+use std::{error::Error, fmt, str::FromStr};
+
+/// A colour for images, written like `#ff8000` in the arguments and SVG
+/// images.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Colour {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseColourError {
+    pub invalid_colour: String,
+}
+
+impl Error for ParseColourError {}
+
+impl fmt::Display for ParseColourError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Invalid colour '{}', it should be six hexadecimal digits like #ff8000",
+            self.invalid_colour
+        )
+    }
+}
+
+impl Colour {
+    #[must_use]
+    pub const fn new(red: u8, green: u8, blue: u8) -> Self {
+        Self { red, green, blue }
+    }
+
+    #[must_use]
+    pub fn rgb(&self) -> [u8; 3] {
+        [self.red, self.green, self.blue]
+    }
+}
+
+impl FromStr for Colour {
+    type Err = ParseColourError;
+
+    /// Six hexadecimal digits, with or without a leading `#`.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let digits = text.trim().strip_prefix('#').unwrap_or(text.trim());
+
+        let component = |index: usize| {
+            digits
+                .get(index..index + 2)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        };
+
+        match (digits.len(), component(0), component(2), component(4)) {
+            (6, Some(red), Some(green), Some(blue)) => Ok(Self::new(red, green, blue)),
+            _ => Err(ParseColourError {
+                invalid_colour: text.to_owned(),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for Colour {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.red, self.green, self.blue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::output::colour::{Colour, ParseColourError};
+
+    #[test]
+    fn it_should_be_parsed_from_hexadecimal_digits() {
+        assert_eq!("#ff8000".parse(), Ok(Colour::new(255, 128, 0)));
+        assert_eq!("FF8000".parse(), Ok(Colour::new(255, 128, 0)));
+    }
+
+    #[test]
+    fn it_should_fail_parsing_anything_else() {
+        for invalid_colour in ["", "#fff", "#ff80001", "#gg8000", "red"] {
+            assert_eq!(
+                invalid_colour.parse::<Colour>(),
+                Err(ParseColourError {
+                    invalid_colour: invalid_colour.to_owned()
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn it_should_be_written_as_hexadecimal_digits() {
+        assert_eq!(Colour::new(255, 128, 0).to_string(), "#ff8000");
+    }
+}
@@ -0,0 +1,479 @@
+use std::{error::Error, fmt};
+
+use crate::domain::{cell::coordinates::Coordinates, grid::Grid};
+
+use super::colour::Colour;
+
+/// Palette index of the pixels of the dead cells and the ruler background.
+pub const DEAD_CELL_PIXEL: u8 = 0;
+/// Palette index of the pixels of the live cells.
+pub const LIVE_CELL_PIXEL: u8 = 1;
+/// Palette index of the pixels of the grid lines and the ruler marks.
+pub const LINE_PIXEL: u8 = 2;
+
+/// Default side of the square drawn for every cell, in pixels.
+pub const DEFAULT_CELL_SIZE: usize = 4;
+/// Biggest side of the square drawn for every cell, in pixels.
+pub const MAX_CELL_SIZE: usize = 100;
+
+/// Most pixels of an image, so that its bitmap fits in memory.
+pub const MAX_PIXELS: usize = 1 << 28;
+
+/// Every cell row and column is numbered on the ruler with a 3x5 pixels
+/// digit, with a space between digits.
+const DIGIT_WIDTH: usize = 3;
+const DIGIT_HEIGHT: usize = 5;
+
+/// Rows of every digit, from top to bottom, with one bit per pixel.
+const DIGITS: [[u8; DIGIT_HEIGHT]; 10] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b001, 0b001, 0b001],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+];
+
+/// Length of the ruler ticks for the numbered rows and columns, and for the
+/// other ones, in pixels.
+const MAJOR_TICK_LENGTH: usize = 3;
+const MINOR_TICK_LENGTH: usize = 1;
+
+/// How the cells are drawn in images.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageStyle {
+    pub cell_size: usize,    // Side of the square drawn for every cell, in pixels
+    pub grid_lines: bool,    // Draw a one pixel line around every cell
+    pub ruler: bool,         // Number the rows and columns on the top and left sides
+    pub live_colour: Colour, // Colour of the live cells
+    pub dead_colour: Colour, // Colour of the dead cells and the background
+    pub line_colour: Colour, // Colour of the grid lines and the ruler
+}
+
+impl Default for ImageStyle {
+    fn default() -> Self {
+        Self {
+            cell_size: DEFAULT_CELL_SIZE,
+            grid_lines: false,
+            ruler: false,
+            live_colour: Colour::new(255, 255, 255),
+            dead_colour: Colour::new(0, 0, 0),
+            line_colour: Colour::new(128, 128, 128),
+        }
+    }
+}
+
+impl ImageStyle {
+    /// Colours in the order of their palette indexes.
+    #[must_use]
+    pub fn palette(&self) -> [Colour; 3] {
+        [self.dead_colour, self.live_colour, self.line_colour]
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ImageTooBigError {
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Error for ImageTooBigError {}
+
+impl fmt::Display for ImageTooBigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "the image is {}x{} pixels, more than the limit of {} pixels",
+            self.width, self.height, MAX_PIXELS
+        )
+    }
+}
+
+/// A rectangle in an image, in pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rect {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+/// A number on the ruler. The position is the left top corner of its first
+/// digit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Label {
+    pub x: usize,
+    pub y: usize,
+    pub text: String,
+}
+
+impl Label {
+    /// Width of the label, in pixels.
+    #[must_use]
+    pub fn width(&self) -> usize {
+        label_width(&self.text)
+    }
+}
+
+/// Where every part of the image of a grid is drawn. The grid is on the
+/// right bottom corner, after the ruler if there is one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Layout {
+    pub width: usize,
+    pub height: usize,
+    rows: usize,
+    columns: usize,
+    left: usize,
+    top: usize,
+    /// Rows and columns between numbers on the ruler.
+    ruler_step: usize,
+    style: ImageStyle,
+}
+
+impl Layout {
+    #[must_use]
+    pub fn new(rows: usize, columns: usize, style: &ImageStyle) -> Self {
+        let ruler_step = ruler_step(rows.max(columns), pitch(style));
+
+        let (left, top) = if style.ruler {
+            let last_row_label = rows.saturating_sub(1) / ruler_step * ruler_step;
+            (
+                label_width(&last_row_label.to_string()) + 1 + MAJOR_TICK_LENGTH,
+                DIGIT_HEIGHT + 1 + MAJOR_TICK_LENGTH,
+            )
+        } else {
+            (0, 0)
+        };
+
+        Self {
+            width: left.saturating_add(grid_length(columns, style)),
+            height: top.saturating_add(grid_length(rows, style)),
+            rows,
+            columns,
+            left,
+            top,
+            ruler_step,
+            style: *style,
+        }
+    }
+
+    /// Pixels of the whole image, None if there are more than `MAX_PIXELS`.
+    #[must_use]
+    pub fn pixels(&self) -> Option<usize> {
+        self.width
+            .checked_mul(self.height)
+            .filter(|pixels| *pixels <= MAX_PIXELS)
+    }
+
+    /// Square where the cell is drawn.
+    #[must_use]
+    pub fn cell(&self, coordinates: &Coordinates) -> Rect {
+        let line = usize::from(self.style.grid_lines);
+        Rect {
+            x: self.left + coordinates.column * pitch(&self.style) + line,
+            y: self.top + coordinates.row * pitch(&self.style) + line,
+            width: self.style.cell_size,
+            height: self.style.cell_size,
+        }
+    }
+
+    /// Lines between the cells and around the grid, if the style has them.
+    #[must_use]
+    pub fn grid_lines(&self) -> Vec<Rect> {
+        if !self.style.grid_lines {
+            return vec![];
+        }
+
+        let grid_width = grid_length(self.columns, &self.style);
+        let grid_height = grid_length(self.rows, &self.style);
+
+        let horizontal = (0..=self.rows).map(|row| Rect {
+            x: self.left,
+            y: self.top + row * pitch(&self.style),
+            width: grid_width,
+            height: 1,
+        });
+        let vertical = (0..=self.columns).map(|column| Rect {
+            x: self.left + column * pitch(&self.style),
+            y: self.top,
+            width: 1,
+            height: grid_height,
+        });
+
+        horizontal.chain(vertical).collect()
+    }
+
+    /// One tick for every row and column on the ruler, longer for the
+    /// numbered ones.
+    #[must_use]
+    pub fn ruler_ticks(&self) -> Vec<Rect> {
+        if !self.style.ruler {
+            return vec![];
+        }
+
+        let tick_length = |index: usize| {
+            if index.is_multiple_of(self.ruler_step) {
+                MAJOR_TICK_LENGTH
+            } else {
+                MINOR_TICK_LENGTH
+            }
+        };
+
+        let columns = (0..self.columns).map(|column| Rect {
+            x: self.left + self.edge(column),
+            y: self.top - tick_length(column),
+            width: 1,
+            height: tick_length(column),
+        });
+        let rows = (0..self.rows).map(|row| Rect {
+            x: self.left - tick_length(row),
+            y: self.top + self.edge(row),
+            width: tick_length(row),
+            height: 1,
+        });
+
+        columns.chain(rows).collect()
+    }
+
+    /// Numbers of the rows and columns on the ruler, every `ruler_step` of
+    /// them. The numbers that do not fit in the image are left out.
+    #[must_use]
+    pub fn ruler_labels(&self) -> Vec<Label> {
+        if !self.style.ruler {
+            return vec![];
+        }
+
+        let columns = (0..self.columns)
+            .step_by(self.ruler_step)
+            .map(|column| Label {
+                x: self.left + self.edge(column),
+                y: 0,
+                text: column.to_string(),
+            })
+            .filter(|label| label.x + label.width() <= self.width);
+
+        let rows = (0..self.rows)
+            .step_by(self.ruler_step)
+            .map(|row| {
+                let text = row.to_string();
+                Label {
+                    x: self.left - MAJOR_TICK_LENGTH - 1 - label_width(&text),
+                    y: self.top + self.edge(row),
+                    text,
+                }
+            })
+            .filter(|label| label.y + DIGIT_HEIGHT <= self.height);
+
+        columns.chain(rows).collect()
+    }
+
+    /// Pixels, from the grid side, to the start of the row or column. It is
+    /// the grid line before it, if there is one.
+    fn edge(&self, index: usize) -> usize {
+        index * pitch(&self.style)
+    }
+}
+
+/// An image of a grid with the palette index of every pixel, row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bitmap {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<u8>,
+}
+
+impl Bitmap {
+    /// # Errors
+    ///
+    /// Will return `Err` if the image has more than `MAX_PIXELS` pixels,
+    /// before any memory is taken for them.
+    pub fn of(grid: &Grid, style: &ImageStyle) -> Result<Self, ImageTooBigError> {
+        let layout = Layout::new(grid.rows(), grid.columns(), style);
+        let pixels = layout.pixels().ok_or(ImageTooBigError {
+            width: layout.width,
+            height: layout.height,
+        })?;
+
+        let mut bitmap = Self {
+            width: layout.width,
+            height: layout.height,
+            pixels: vec![DEAD_CELL_PIXEL; pixels],
+        };
+
+        for line in layout.grid_lines().iter().chain(&layout.ruler_ticks()) {
+            bitmap.fill(line, LINE_PIXEL);
+        }
+
+        for coordinates in grid.iter() {
+            if grid.is_live(&coordinates) {
+                bitmap.fill(&layout.cell(&coordinates), LIVE_CELL_PIXEL);
+            }
+        }
+
+        for label in layout.ruler_labels() {
+            bitmap.write(&label);
+        }
+
+        Ok(bitmap)
+    }
+
+    fn fill(&mut self, rect: &Rect, pixel: u8) {
+        for y in rect.y..rect.y + rect.height {
+            let row = y * self.width;
+            self.pixels[row + rect.x..row + rect.x + rect.width].fill(pixel);
+        }
+    }
+
+    fn write(&mut self, label: &Label) {
+        for (position, digit) in label.text.bytes().enumerate() {
+            let left = label.x + position * (DIGIT_WIDTH + 1);
+
+            for (y, bits) in DIGITS[usize::from(digit - b'0')].iter().enumerate() {
+                for x in 0..DIGIT_WIDTH {
+                    if bits & (1 << (DIGIT_WIDTH - 1 - x)) != 0 {
+                        self.pixels[(label.y + y) * self.width + left + x] = LINE_PIXEL;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Pixels from the start of a cell to the start of the next one.
+fn pitch(style: &ImageStyle) -> usize {
+    style.cell_size + usize::from(style.grid_lines)
+}
+
+/// Pixels taken by the given number of cells, with the grid lines between
+/// them and on both sides. It saturates, too big images are not drawn.
+fn grid_length(cells: usize, style: &ImageStyle) -> usize {
+    cells
+        .saturating_mul(pitch(style))
+        .saturating_add(usize::from(style.grid_lines))
+}
+
+fn label_width(text: &str) -> usize {
+    (text.len() * (DIGIT_WIDTH + 1)).saturating_sub(1)
+}
+
+/// Rows and columns between numbers on the ruler: 10, 20, 50, 100, 200...
+/// The first one that leaves a pixel between the widest numbers.
+fn ruler_step(cells: usize, pitch: usize) -> usize {
+    let widest_label = label_width(&cells.to_string()).max(DIGIT_HEIGHT);
+
+    let mut magnitude = 10;
+    loop {
+        for step in [magnitude, 2 * magnitude, 5 * magnitude] {
+            if step * pitch > widest_label {
+                return step;
+            }
+        }
+        magnitude *= 10;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::domain::{
+        grid::Grid,
+        output::image::{Bitmap, ImageStyle, ImageTooBigError, Label, Layout},
+    };
+
+    fn style(cell_size: usize, grid_lines: bool, ruler: bool) -> ImageStyle {
+        ImageStyle {
+            cell_size,
+            grid_lines,
+            ruler,
+            ..ImageStyle::default()
+        }
+    }
+
+    #[test]
+    fn every_cell_should_be_drawn_as_a_square() {
+        let bitmap = Bitmap::of(&Grid::from_str("⬜⬛").unwrap(), &style(2, false, false)).unwrap();
+
+        assert_eq!((bitmap.width, bitmap.height), (4, 2));
+        assert_eq!(bitmap.pixels, vec![1, 1, 0, 0, 1, 1, 0, 0]);
+    }
+
+    #[test]
+    fn grid_lines_should_be_drawn_around_every_cell() {
+        let bitmap = Bitmap::of(&Grid::from_str("⬜⬛").unwrap(), &style(1, true, false)).unwrap();
+
+        assert_eq!((bitmap.width, bitmap.height), (5, 3));
+        assert_eq!(
+            bitmap.pixels,
+            vec![
+                2, 2, 2, 2, 2, //
+                2, 1, 2, 0, 2, //
+                2, 2, 2, 2, 2,
+            ]
+        );
+    }
+
+    #[test]
+    fn images_that_do_not_fit_in_memory_should_not_be_drawn() {
+        let grid = Grid::of_dead_cells(2000, 2000);
+
+        assert_eq!(
+            Bitmap::of(&grid, &style(100, false, false)),
+            Err(ImageTooBigError {
+                width: 200_000,
+                height: 200_000
+            })
+        );
+        assert_eq!(
+            Layout::new(usize::MAX, 2, &style(2, false, false)).pixels(),
+            None
+        );
+    }
+
+    #[test]
+    fn the_ruler_should_number_every_ten_rows_and_columns() {
+        let layout = Layout::new(25, 25, &style(2, false, true));
+
+        let column_labels: Vec<Label> = layout
+            .ruler_labels()
+            .into_iter()
+            .filter(|label| label.y == 0)
+            .collect();
+
+        // The grid starts after the row numbers, two digits and a tick
+        assert_eq!(layout.width, 7 + 1 + 3 + 25 * 2);
+        assert_eq!(
+            column_labels,
+            vec![
+                Label {
+                    x: 11,
+                    y: 0,
+                    text: "0".to_owned()
+                },
+                Label {
+                    x: 31,
+                    y: 0,
+                    text: "10".to_owned()
+                },
+                Label {
+                    x: 51,
+                    y: 0,
+                    text: "20".to_owned()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn the_ruler_should_leave_out_numbers_that_would_overlap() {
+        let labels = Layout::new(100, 100, &style(1, false, true)).ruler_labels();
+
+        assert!(labels
+            .iter()
+            .all(|label| label.text.parse::<usize>().unwrap() % 20 == 0));
+    }
+}
@@ -1,4 +1,12 @@
+pub mod age_colours;
+pub mod colour;
+pub mod image;
 pub mod logger;
 pub mod printer;
+pub mod recorder;
+pub mod render_mode;
+pub mod renderer;
+pub mod snapshot;
+pub mod svg;
 
 pub const CLEAR_SCREEN: &str = "\x1B[2J\x1B[1;1H";
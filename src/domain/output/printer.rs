@@ -1,4 +1,10 @@
 pub trait Printer {
     fn clear(&self);
     fn print(&self, output: &str);
+
+    /// It replaces the frame on the screen with a new one.
+    fn print_frame(&self, frame: &str) {
+        self.clear();
+        self.print(frame);
+    }
 }
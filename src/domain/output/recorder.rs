@@ -0,0 +1,7 @@
+use crate::domain::grid::Grid;
+
+/// It keeps every generation shown in a game, for example to save the game
+/// as an animation once it ends.
+pub trait Recorder {
+    fn record(&self, generation: &Grid);
+}
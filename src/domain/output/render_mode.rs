@@ -0,0 +1,195 @@
+use std::{error::Error, fmt, str::FromStr};
+
+use crate::domain::{
+    cell::{alphabet::Alphabet, coordinates::Coordinates},
+    grid::Grid,
+    output::renderer::display_width,
+};
+
+/// How the grid cells are drawn in the terminal.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RenderMode {
+    /// One alphabet char per cell, ⬜/⬛ by default.
+    #[default]
+    Text,
+    /// One ASCII char per cell: `#` for live cells and `.` for dead cells.
+    Ascii,
+    /// One char for every two rows of cells: `▀`, `▄`, `█` or a space.
+    HalfBlock,
+    /// One Braille char for every block of four rows and two columns.
+    Braille,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseRenderModeError {
+    pub invalid_render_mode: String,
+}
+
+impl Error for ParseRenderModeError {}
+
+impl fmt::Display for ParseRenderModeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid render mode '{}'", self.invalid_render_mode)
+    }
+}
+
+const RENDER_MODES: [(RenderMode, &str); 4] = [
+    (RenderMode::Text, "text"),
+    (RenderMode::Ascii, "ascii"),
+    (RenderMode::HalfBlock, "half-block"),
+    (RenderMode::Braille, "braille"),
+];
+
+impl FromStr for RenderMode {
+    type Err = ParseRenderModeError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        RENDER_MODES
+            .iter()
+            .find(|(_, name)| name.eq_ignore_ascii_case(text.trim()))
+            .map(|(render_mode, _)| *render_mode)
+            .ok_or_else(|| ParseRenderModeError {
+                invalid_render_mode: text.to_owned(),
+            })
+    }
+}
+
+impl fmt::Display for RenderMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (_, name) = RENDER_MODES
+            .iter()
+            .find(|(render_mode, _)| render_mode == self)
+            .expect("all render modes should have a name");
+        write!(f, "{name}")
+    }
+}
+
+const ASCII_ALPHABET: Alphabet = Alphabet::new('#', '.');
+
+/// First Braille pattern, with no dots.
+const BRAILLE_BLANK: u32 = 0x2800;
+
+/// Bit of the Braille dot for every row and column of the block.
+const BRAILLE_DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+impl RenderMode {
+    /// It renders the grid, one line per row of chars. The alphabet is only
+    /// used by the text mode.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if a Braille block is not a char, but every combination of
+    /// the eight dots is one.
+    #[must_use]
+    pub fn render(&self, grid: &Grid, alphabet: &Alphabet) -> String {
+        match self {
+            RenderMode::Text => grid.to_string_with(alphabet),
+            RenderMode::Ascii => grid.to_string_with(&ASCII_ALPHABET),
+            RenderMode::HalfBlock => {
+                render_blocks(grid, 2, 1, |is_live| match (is_live(0, 0), is_live(1, 0)) {
+                    (true, true) => '█',
+                    (true, false) => '▀',
+                    (false, true) => '▄',
+                    (false, false) => ' ',
+                })
+            }
+            RenderMode::Braille => render_blocks(grid, 4, 2, |is_live| {
+                let mut dots = 0;
+                for (row, row_dots) in BRAILLE_DOTS.iter().enumerate() {
+                    for (column, dot) in row_dots.iter().enumerate() {
+                        if is_live(row, column) {
+                            dots |= dot;
+                        }
+                    }
+                }
+                char::from_u32(BRAILLE_BLANK + dots).expect("Braille patterns should be chars")
+            }),
+        }
+    }
+}
+
+impl RenderMode {
+    /// Width in columns of a cell painted by age, the same as its char in
+    /// this mode. Modes drawing several cells per char cannot be painted
+    /// cell by cell, so they have none.
+    #[must_use]
+    pub fn cell_width(&self, alphabet: &Alphabet) -> Option<usize> {
+        match self {
+            RenderMode::Text => {
+                Some(display_width(alphabet.live()).max(display_width(alphabet.dead())))
+            }
+            RenderMode::Ascii => Some(1),
+            RenderMode::HalfBlock | RenderMode::Braille => None,
+        }
+    }
+}
+
+/// It renders every block of cells with the given size as one char. Cells
+/// outside the grid, in the blocks on the bottom and right edges, are dead.
+fn render_blocks(
+    grid: &Grid,
+    block_rows: usize,
+    block_columns: usize,
+    char_for_block: impl Fn(&dyn Fn(usize, usize) -> bool) -> char,
+) -> String {
+    let mut output = String::new();
+
+    for top in (0..grid.rows()).step_by(block_rows) {
+        for left in (0..grid.columns()).step_by(block_columns) {
+            let is_live = |row: usize, column: usize| {
+                let coordinates = Coordinates::new(top + row, left + column);
+                grid.position_is_valid(&coordinates) && grid.is_live(&coordinates)
+            };
+            output.push(char_for_block(&is_live));
+        }
+        output.push('\n');
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::{
+        cell::alphabet::Alphabet,
+        fixtures::glider,
+        output::render_mode::{ParseRenderModeError, RenderMode},
+    };
+
+    fn render(render_mode: RenderMode) -> String {
+        render_mode.render(&glider(), &Alphabet::default())
+    }
+
+    #[test]
+    fn the_text_mode_should_use_the_alphabet() {
+        assert_eq!(render(RenderMode::Text), "⬛⬜⬛\n⬛⬛⬜\n⬜⬜⬜\n");
+    }
+
+    #[test]
+    fn the_ascii_mode_should_use_one_ascii_char_per_cell() {
+        assert_eq!(render(RenderMode::Ascii), ".#.\n..#\n###\n");
+    }
+
+    #[test]
+    fn the_half_block_mode_should_draw_two_rows_per_char() {
+        assert_eq!(render(RenderMode::HalfBlock), " ▀▄\n▀▀▀\n");
+    }
+
+    #[test]
+    fn the_braille_mode_should_draw_four_rows_and_two_columns_per_char() {
+        // Dots 3, 4 and 6 on the left char and dots 2 and 3 on the right one
+        assert_eq!(render(RenderMode::Braille), "⠬⠆\n");
+    }
+
+    #[test]
+    fn a_render_mode_should_be_parsed_from_its_name() {
+        assert_eq!("half-block".parse(), Ok(RenderMode::HalfBlock));
+        assert_eq!(RenderMode::Braille.to_string(), "braille");
+        assert_eq!(
+            "sixel".parse::<RenderMode>(),
+            Err(ParseRenderModeError {
+                invalid_render_mode: "sixel".to_owned()
+            })
+        );
+    }
+}
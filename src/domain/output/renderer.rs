@@ -0,0 +1,246 @@
+use std::fmt::Write;
+
+use super::CLEAR_SCREEN;
+
+/// Escape sequence that resets the colours.
+const RESET_STYLE: &str = "\x1B[0m";
+
+/// A visible char and the colour escape sequence it is drawn with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Glyph {
+    style: String,
+    char: char,
+}
+
+/// It renders every frame as the changes from the previous one, so only the
+/// cells that changed are written to the terminal.
+///
+/// Frames can be coloured with SGR escape sequences (`ESC[...m`). Each
+/// sequence replaces the previous one until it is reset with `ESC[0m`.
+///
+/// The first frame, a frame with a different size or the first frame after
+/// calling `invalidate` (for example, when the terminal is resized) is
+/// rendered in full. After the changes, the cursor goes below the frame, as
+/// after a full one, so whatever is printed next does not overwrite it.
+#[derive(Debug, Default)]
+pub struct IncrementalRenderer {
+    previous: Option<Vec<Vec<Glyph>>>,
+}
+
+impl IncrementalRenderer {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The next frame will be rendered in full.
+    pub fn invalidate(&mut self) {
+        self.previous = None;
+    }
+
+    /// It returns what has to be written to the terminal to show the frame:
+    /// the changed chars, each run of them preceded by the escape sequence
+    /// that moves the cursor to its position, and the sequence that moves it
+    /// below the frame.
+    pub fn render(&mut self, frame: &str) -> String {
+        let lines: Vec<Vec<Glyph>> = frame.lines().map(glyphs).collect();
+
+        let output = match &self.previous {
+            Some(previous) if has_same_shape(previous, &lines) => diff(previous, &lines),
+            _ => format!("{CLEAR_SCREEN}{frame}"),
+        };
+
+        self.previous = Some(lines);
+
+        output
+    }
+}
+
+/// It splits the line into its visible chars, each one with the colour
+/// it is drawn with.
+fn glyphs(line: &str) -> Vec<Glyph> {
+    let mut glyphs = vec![];
+    let mut style = String::new();
+    let mut chars = line.chars();
+
+    while let Some(char) = chars.next() {
+        if char == '\x1B' {
+            let sequence: String = chars.by_ref().take_while(|char| *char != 'm').collect();
+            style = if sequence == "[0" {
+                String::new()
+            } else {
+                format!("\x1B{sequence}m")
+            };
+        } else {
+            glyphs.push(Glyph {
+                style: style.clone(),
+                char,
+            });
+        }
+    }
+
+    glyphs
+}
+
+fn has_same_shape(previous: &[Vec<Glyph>], lines: &[Vec<Glyph>]) -> bool {
+    previous.len() == lines.len()
+        && previous
+            .iter()
+            .zip(lines)
+            .all(|(previous_line, line)| previous_line.len() == line.len())
+}
+
+fn diff(previous: &[Vec<Glyph>], lines: &[Vec<Glyph>]) -> String {
+    let mut output = String::new();
+    let mut current_style = "";
+
+    for (row, (previous_line, line)) in previous.iter().zip(lines).enumerate() {
+        let mut column = 0;
+        let mut cursor_is_here = false;
+
+        for (previous_glyph, glyph) in previous_line.iter().zip(line) {
+            if previous_glyph == glyph {
+                cursor_is_here = false;
+            } else {
+                if !cursor_is_here {
+                    // Terminal rows and columns start at 1
+                    let _ = write!(output, "\x1B[{};{}H", row + 1, column + 1);
+                }
+                if glyph.style != current_style {
+                    output.push_str(if glyph.style.is_empty() {
+                        RESET_STYLE
+                    } else {
+                        &glyph.style
+                    });
+                    current_style = &glyph.style;
+                }
+                output.push(glyph.char);
+                cursor_is_here = true;
+            }
+            column += display_width(glyph.char);
+        }
+    }
+
+    if !current_style.is_empty() {
+        output.push_str(RESET_STYLE);
+    }
+    if !output.is_empty() {
+        let _ = write!(output, "\x1B[{};1H", lines.len() + 1);
+    }
+
+    output
+}
+
+/// Number of terminal columns the char takes. Emojis and East Asian wide
+/// chars take two columns.
+pub(crate) fn display_width(char: char) -> usize {
+    match u32::from(char) {
+        0x1100..=0x115F
+        | 0x25FD..=0x25FE
+        | 0x2B1B..=0x2B1C
+        | 0x2B50
+        | 0x2B55
+        | 0x2E80..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F300..=0x1F64F
+        | 0x1F7E0..=0x1F7EB
+        | 0x1F900..=0x1F9FF => 2,
+        _ => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::output::{renderer::IncrementalRenderer, CLEAR_SCREEN};
+
+    #[test]
+    fn the_first_frame_should_be_rendered_in_full() {
+        let mut renderer = IncrementalRenderer::new();
+
+        assert_eq!(
+            renderer.render("#.\n.#\n"),
+            format!("{CLEAR_SCREEN}#.\n.#\n")
+        );
+    }
+
+    #[test]
+    fn only_the_changed_chars_should_be_rendered() {
+        let mut renderer = IncrementalRenderer::new();
+        let _ = renderer.render("#..\n...\n");
+
+        assert_eq!(
+            renderer.render("..#\n.##\n"),
+            "\x1B[1;1H.\x1B[1;3H#\x1B[2;2H##\x1B[3;1H"
+        );
+    }
+
+    #[test]
+    fn nothing_should_be_rendered_when_the_frame_does_not_change() {
+        let mut renderer = IncrementalRenderer::new();
+        let _ = renderer.render("#.\n");
+
+        assert_eq!(renderer.render("#.\n"), "");
+    }
+
+    #[test]
+    fn emojis_should_take_two_columns() {
+        let mut renderer = IncrementalRenderer::new();
+        let _ = renderer.render("⬛⬛⬛\n");
+
+        assert_eq!(renderer.render("⬛⬛⬜\n"), "\x1B[1;5H⬜\x1B[2;1H");
+    }
+
+    #[test]
+    fn the_changed_chars_should_keep_their_colours() {
+        let mut renderer = IncrementalRenderer::new();
+        let _ = renderer.render("\x1B[48;5;21m  \x1B[0m  \n");
+
+        assert_eq!(
+            renderer.render("\x1B[48;5;21m  \x1B[48;5;46m  \x1B[0m\n"),
+            "\x1B[1;3H\x1B[48;5;46m  \x1B[0m\x1B[2;1H"
+        );
+    }
+
+    #[test]
+    fn a_change_of_colour_should_be_rendered() {
+        let mut renderer = IncrementalRenderer::new();
+        let _ = renderer.render("\x1B[48;5;21m \x1B[0m \n");
+
+        assert_eq!(
+            renderer.render(" \x1B[48;5;21m \x1B[0m\n"),
+            "\x1B[1;1H \x1B[48;5;21m \x1B[0m\x1B[2;1H"
+        );
+    }
+
+    #[test]
+    fn the_cursor_should_be_left_below_the_frame() {
+        let mut renderer = IncrementalRenderer::new();
+        let _ = renderer.render("#..\n...\n...\n");
+
+        let output = renderer.render("#..\n.#.\n...\n");
+
+        assert!(output.ends_with("\x1B[4;1H"));
+    }
+
+    #[test]
+    fn a_frame_with_a_different_size_should_be_rendered_in_full() {
+        let mut renderer = IncrementalRenderer::new();
+        let _ = renderer.render("#.\n");
+
+        assert_eq!(renderer.render("#..\n"), format!("{CLEAR_SCREEN}#..\n"));
+    }
+
+    #[test]
+    fn the_frame_should_be_rendered_in_full_after_invalidating_the_previous_one() {
+        let mut renderer = IncrementalRenderer::new();
+        let _ = renderer.render("#.\n");
+
+        renderer.invalidate();
+
+        assert_eq!(renderer.render("#.\n"), format!("{CLEAR_SCREEN}#.\n"));
+    }
+}
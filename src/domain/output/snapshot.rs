@@ -0,0 +1,118 @@
+use std::path::Path;
+
+/// Image formats for snapshots of the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Png,
+    Svg,
+}
+
+impl ImageFormat {
+    /// Format for the extension of the given file name, if it is a known one.
+    #[must_use]
+    pub fn from_file_name(file_name: &str) -> Option<Self> {
+        let extension = Path::new(file_name).extension()?.to_str()?;
+
+        match extension.to_lowercase().as_str() {
+            "png" => Some(ImageFormat::Png),
+            "svg" => Some(ImageFormat::Svg),
+            _ => None,
+        }
+    }
+}
+
+/// Generations saved as images.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Snapshots {
+    /// Only the given generation.
+    Generation(u32),
+    /// The first generation and then every given number of generations.
+    Every(u32),
+}
+
+impl Default for Snapshots {
+    /// Only the initial pattern.
+    fn default() -> Self {
+        Snapshots::Generation(0)
+    }
+}
+
+impl Snapshots {
+    #[must_use]
+    pub fn includes(&self, generation: u32) -> bool {
+        match self {
+            Snapshots::Generation(snapshot) => generation == *snapshot,
+            Snapshots::Every(generations) => generation.is_multiple_of(*generations),
+        }
+    }
+
+    /// Number of generations to play to take all the snapshots, or none if
+    /// the game has to be played until the end.
+    #[must_use]
+    pub fn generations_needed(&self) -> Option<u32> {
+        match self {
+            Snapshots::Generation(snapshot) => Some(snapshot.saturating_add(1)),
+            Snapshots::Every(_) => None,
+        }
+    }
+}
+
+/// File for the snapshot of the generation: the generation number, padded
+/// to the digits of the last one so the files are sorted, goes before the
+/// extension. For example, `glider-007.png` for `glider.png`.
+#[must_use]
+pub fn numbered_file_name(file_name: &str, generation: u32, last_generation: u32) -> String {
+    let digits = last_generation.to_string().len();
+    let number = format!("{generation:0digits$}");
+
+    match Path::new(file_name)
+        .extension()
+        .and_then(|extension| extension.to_str())
+    {
+        Some(extension) => format!(
+            "{}-{number}.{extension}",
+            &file_name[..file_name.len() - extension.len() - 1]
+        ),
+        None => format!("{file_name}-{number}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::output::snapshot::{numbered_file_name, ImageFormat, Snapshots};
+
+    #[test]
+    fn the_image_format_should_be_the_one_for_the_file_extension() {
+        assert_eq!(
+            ImageFormat::from_file_name("glider.PNG"),
+            Some(ImageFormat::Png)
+        );
+        assert_eq!(
+            ImageFormat::from_file_name("docs/glider.svg"),
+            Some(ImageFormat::Svg)
+        );
+        assert_eq!(ImageFormat::from_file_name("glider.gif"), None);
+    }
+
+    #[test]
+    fn it_should_take_a_snapshot_of_one_generation_or_every_few_generations() {
+        let taken = |snapshots: Snapshots| -> Vec<u32> {
+            (0..10)
+                .filter(|generation| snapshots.includes(*generation))
+                .collect()
+        };
+
+        assert_eq!(taken(Snapshots::Generation(4)), vec![4]);
+        assert_eq!(taken(Snapshots::Every(3)), vec![0, 3, 6, 9]);
+    }
+
+    #[test]
+    fn the_generation_number_should_go_before_the_extension() {
+        assert_eq!(
+            numbered_file_name("out/glider.png", 7, 120),
+            "out/glider-007.png"
+        );
+        assert_eq!(numbered_file_name("glider.svg", 12, 12), "glider-12.svg");
+        assert_eq!(numbered_file_name("glider", 3, 9), "glider-3");
+    }
+}
@@ -0,0 +1,101 @@
+use std::fmt::Write;
+
+use crate::domain::grid::Grid;
+
+use super::image::{ImageStyle, Layout, Rect};
+
+/// It draws the grid as an SVG image with the same layout as the bitmap
+/// images, one rectangle per live cell, grid line and ruler tick.
+#[must_use]
+pub fn svg(grid: &Grid, style: &ImageStyle) -> String {
+    let layout = Layout::new(grid.rows(), grid.columns(), style);
+
+    let mut output = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         viewBox=\"0 0 {width} {height}\" shape-rendering=\"crispEdges\">\n",
+        width = layout.width,
+        height = layout.height,
+    );
+
+    let rect = |output: &mut String, rect: &Rect, fill: &str| {
+        let _ = writeln!(
+            output,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{fill}\"/>",
+            rect.x, rect.y, rect.width, rect.height
+        );
+    };
+
+    let background = Rect {
+        x: 0,
+        y: 0,
+        width: layout.width,
+        height: layout.height,
+    };
+    rect(&mut output, &background, &style.dead_colour.to_string());
+
+    let line_colour = style.line_colour.to_string();
+    for line in layout.grid_lines().iter().chain(&layout.ruler_ticks()) {
+        rect(&mut output, line, &line_colour);
+    }
+
+    let live_cell_colour = style.live_colour.to_string();
+    for coordinates in grid.iter() {
+        if grid.is_live(&coordinates) {
+            rect(&mut output, &layout.cell(&coordinates), &live_cell_colour);
+        }
+    }
+
+    // The digits of the bitmap ruler are 5 pixels high
+    for label in layout.ruler_labels() {
+        let _ = writeln!(
+            output,
+            "<text x=\"{}\" y=\"{}\" font-family=\"monospace\" font-size=\"7\" fill=\"{line_colour}\">{}</text>",
+            label.x,
+            label.y + 5,
+            label.text
+        );
+    }
+
+    output.push_str("</svg>\n");
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::domain::{
+        grid::Grid,
+        output::{colour::Colour, image::ImageStyle, svg::svg},
+    };
+
+    #[test]
+    fn it_should_draw_a_rectangle_for_every_live_cell() {
+        let style = ImageStyle {
+            cell_size: 10,
+            live_colour: Colour::new(255, 0, 0),
+            ..ImageStyle::default()
+        };
+
+        assert_eq!(
+            svg(&Grid::from_str("⬛⬜").unwrap(), &style),
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"20\" height=\"10\" \
+             viewBox=\"0 0 20 10\" shape-rendering=\"crispEdges\">\n\
+             <rect x=\"0\" y=\"0\" width=\"20\" height=\"10\" fill=\"#000000\"/>\n\
+             <rect x=\"10\" y=\"0\" width=\"10\" height=\"10\" fill=\"#ff0000\"/>\n\
+             </svg>\n"
+        );
+    }
+
+    #[test]
+    fn it_should_number_the_rows_and_columns_on_the_ruler() {
+        let style = ImageStyle {
+            ruler: true,
+            ..ImageStyle::default()
+        };
+
+        let image = svg(&Grid::of_dead_cells(3, 3), &style);
+
+        assert_eq!(image.matches(">0</text>").count(), 2);
+    }
+}
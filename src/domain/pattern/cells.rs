@@ -0,0 +1,216 @@
+use std::{error::Error, fmt, fmt::Write};
+
+use crate::domain::{
+    cell::coordinates::Coordinates,
+    grid::{bits::BitRows, Grid},
+};
+
+use super::Pattern;
+
+const COMMENT: char = '!';
+const NAME_PREFIX: &str = "Name:";
+const LIVE: char = 'O';
+const DEAD: char = '.';
+
+/// A row contains a char other than `O` or `.`. Lines and columns start
+/// at 1.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseCellsError {
+    pub line: usize,
+    pub column: usize,
+    pub invalid_char: char,
+}
+
+impl Error for ParseCellsError {}
+
+impl fmt::Display for ParseCellsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Invalid char '{}' for cells pattern at line {}, column {}",
+            self.invalid_char, self.line, self.column
+        )
+    }
+}
+
+/// It parses a pattern in the plaintext `.cells` format.
+///
+/// Lines starting with `!` are comments, and a `!Name: ...` comment gives
+/// the pattern name. Every other line is a row with `O` for live cells and
+/// `.` for dead cells. Rows can be shorter than the widest one: the missing
+/// cells are dead.
+///
+/// # Errors
+///
+/// Will return `Err` if a row contains a char other than `O` or `.`.
+pub fn parse_cells(text: &str) -> Result<Pattern, ParseCellsError> {
+    let mut name = None;
+    let mut comments = vec![];
+    let mut rows = vec![];
+
+    for (index, line) in text.lines().map(str::trim_end).enumerate() {
+        if let Some(comment) = line.strip_prefix(COMMENT) {
+            match comment.strip_prefix(NAME_PREFIX) {
+                Some(pattern_name) => name = Some(pattern_name.trim().to_owned()),
+                None => comments.push(comment.trim().to_owned()),
+            }
+            continue;
+        }
+
+        let leading_spaces = line.chars().take_while(|c| c.is_whitespace()).count();
+        let row = line
+            .trim_start()
+            .chars()
+            .enumerate()
+            .map(|(position, c)| match c {
+                LIVE | 'o' | '*' => Ok(true),
+                DEAD => Ok(false),
+                _ => Err(ParseCellsError {
+                    line: index + 1,
+                    column: leading_spaces + position + 1,
+                    invalid_char: c,
+                }),
+            })
+            .collect::<Result<Vec<bool>, _>>()?;
+        rows.push(row);
+    }
+
+    while rows.last().is_some_and(Vec::is_empty) {
+        rows.pop();
+    }
+
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let mut cells = BitRows::new(rows.len(), columns);
+    for (row, cell_row) in rows.iter().enumerate() {
+        for (column, live) in cell_row.iter().enumerate() {
+            cells.set(row, column, *live);
+        }
+    }
+
+    Ok(Pattern {
+        grid: Grid::with_cells(cells),
+        name,
+        comments,
+        rule: None,
+    })
+}
+
+/// It writes a pattern in the plaintext `.cells` format.
+///
+/// The format has no place for the rule, so it is left out.
+#[must_use]
+pub fn write_cells(pattern: &Pattern) -> String {
+    let mut output = String::new();
+
+    if let Some(name) = &pattern.name {
+        writeln!(output, "{COMMENT}{NAME_PREFIX} {name}").unwrap();
+    }
+    for comment in &pattern.comments {
+        writeln!(output, "{COMMENT}{comment}").unwrap();
+    }
+
+    let grid = &pattern.grid;
+    for row in 0..grid.rows() {
+        for column in 0..grid.columns() {
+            output.push(if grid.is_live(&Coordinates::new(row, column)) {
+                LIVE
+            } else {
+                DEAD
+            });
+        }
+        writeln!(output).unwrap();
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::domain::{
+        fixtures::glider,
+        grid::Grid,
+        pattern::{
+            cells::{parse_cells, write_cells, ParseCellsError},
+            Pattern,
+        },
+    };
+
+    #[test]
+    fn it_should_parse_a_pattern() {
+        let pattern = parse_cells(
+            "!Name: Glider
+!The smallest spaceship
+.O.
+..O
+OOO",
+        )
+        .unwrap();
+
+        assert_eq!(pattern.grid, glider());
+        assert_eq!(pattern.name, Some("Glider".to_owned()));
+        assert_eq!(pattern.comments, vec!["The smallest spaceship"]);
+    }
+
+    #[test]
+    fn short_rows_are_filled_with_dead_cells() {
+        let pattern = parse_cells(".O\n\nOOO\n").unwrap();
+
+        assert_eq!(
+            pattern.grid,
+            Grid::from_str(
+                "⬛⬜⬛
+                 ⬛⬛⬛
+                 ⬜⬜⬜",
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn it_should_fail_showing_the_position_of_the_invalid_char() {
+        assert_eq!(
+            parse_cells("!Glider\n.O.\n .X."),
+            Err(ParseCellsError {
+                line: 3,
+                column: 3,
+                invalid_char: 'X'
+            })
+        );
+    }
+
+    #[test]
+    fn parse_errors_should_be_displayed() {
+        assert_eq!(
+            ParseCellsError {
+                line: 3,
+                column: 3,
+                invalid_char: 'X'
+            }
+            .to_string(),
+            "Invalid char 'X' for cells pattern at line 3, column 3"
+        );
+    }
+
+    #[test]
+    fn it_should_write_a_pattern() {
+        let pattern = Pattern {
+            name: Some("Glider".to_owned()),
+            ..Pattern::from(glider())
+        };
+
+        assert_eq!(write_cells(&pattern), "!Name: Glider\n.O.\n..O\nOOO\n");
+    }
+
+    #[test]
+    fn it_should_read_what_it_writes() {
+        let pattern = Pattern {
+            name: Some("Glider".to_owned()),
+            comments: vec!["The smallest spaceship".to_owned()],
+            ..Pattern::from(glider())
+        };
+
+        assert_eq!(parse_cells(&write_cells(&pattern)).unwrap(), pattern);
+    }
+}
@@ -0,0 +1,475 @@
+use std::{error::Error, fmt, fmt::Write};
+
+use crate::domain::{
+    cell::coordinates::Coordinates,
+    grid::{bits::BitRows, Grid},
+    rule::Rule,
+    universe::{position::Position, Universe},
+};
+
+use super::{rle::MAX_CELLS, Pattern};
+
+pub const LIFE_105_HEADER: &str = "#Life 1.05";
+pub const LIFE_106_HEADER: &str = "#Life 1.06";
+
+const LIVE: char = '*';
+const DEAD: char = '.';
+
+/// Highest neighbour count in a rule.
+const MAX_NEIGHBORS: usize = 8;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseLifeError {
+    /// The first line is not the `#Life 1.05` or `#Life 1.06` header.
+    MissingHeader,
+    /// A line should have two integer coordinates (`x y`) but it has not,
+    /// or the cells of the block it starts are too far from (0,0).
+    InvalidCoordinates { line: String },
+    /// The `#R` line does not contain a valid rule-string.
+    InvalidRule { rule: String },
+    /// A cell row contains a char other than `*` or `.`.
+    InvalidChar { invalid_char: char },
+    /// The live cells bounding box is bigger than `MAX_CELLS`.
+    TooBig { rows: u64, columns: u64 },
+}
+
+impl Error for ParseLifeError {}
+
+impl fmt::Display for ParseLifeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseLifeError::MissingHeader => write!(f, "Missing Life 1.05/1.06 header line"),
+            ParseLifeError::InvalidCoordinates { line } => {
+                write!(f, "Invalid coordinates '{line}' for Life pattern")
+            }
+            ParseLifeError::InvalidRule { rule } => {
+                write!(f, "Invalid rule '{rule}' in Life pattern")
+            }
+            ParseLifeError::InvalidChar { invalid_char } => {
+                write!(f, "Invalid char '{invalid_char}' for Life pattern")
+            }
+            ParseLifeError::TooBig { rows, columns } => write!(
+                f,
+                "Life pattern of {columns}x{rows} cells is bigger than the limit of {MAX_CELLS} cells"
+            ),
+        }
+    }
+}
+
+/// It parses a pattern in the Life 1.06 format: the `#Life 1.06` header
+/// followed by one `x y` line for every live cell.
+///
+/// Coordinates can be negative. The grid is the bounding box of the live
+/// cells.
+///
+/// # Errors
+///
+/// Will return `Err` if the header is missing, a line does not contain
+/// two integer coordinates or the live cells are too far apart.
+pub fn parse_life_106(text: &str) -> Result<Pattern, ParseLifeError> {
+    let lines = lines_after_header(text, LIFE_106_HEADER)?;
+
+    let live_cells = lines
+        .filter(|line| !line.starts_with('#'))
+        .map(parse_coordinates)
+        .collect::<Result<Vec<Position>, _>>()?;
+
+    Ok(Pattern::from(grid_from_live_cells(live_cells)?))
+}
+
+/// It writes a pattern in the Life 1.06 format. The coordinates are the
+/// grid ones, with the left top corner at (0,0).
+#[must_use]
+pub fn write_life_106(pattern: &Pattern) -> String {
+    let mut output = String::new();
+    writeln!(output, "{LIFE_106_HEADER}").unwrap();
+
+    let grid = &pattern.grid;
+    for coordinates in grid.iter().filter(|coordinates| grid.is_live(coordinates)) {
+        writeln!(output, "{} {}", coordinates.column, coordinates.row).unwrap();
+    }
+
+    output
+}
+
+/// It parses a pattern in the Life 1.05 format.
+///
+/// After the `#Life 1.05` header come `#D` description lines, an optional
+/// `#N` (normal rules) or `#R` line with the rule in S/B notation, and
+/// blocks of cells. Every block starts with a `#P x y` line with the
+/// position of its left top corner, followed by rows with `*` for live
+/// cells and `.` for dead cells. The grid is the bounding box of the live
+/// cells.
+///
+/// # Errors
+///
+/// Will return `Err` if the header is missing, the rule or a block
+/// position is invalid, a row contains an invalid char or the live cells
+/// are too far apart.
+pub fn parse_life_105(text: &str) -> Result<Pattern, ParseLifeError> {
+    let lines = lines_after_header(text, LIFE_105_HEADER)?;
+
+    let mut comments = vec![];
+    let mut rule = None;
+    let mut live_cells = vec![];
+    let mut block_origin = Position::new(0, 0);
+    let mut block_line = "#P 0 0";
+    let mut row = 0;
+
+    for line in lines {
+        if let Some(description) = line.strip_prefix("#D") {
+            comments.push(description.trim().to_owned());
+        } else if line.starts_with("#N") {
+            rule = Some(Rule::conway());
+        } else if let Some(rule_string) = line.strip_prefix("#R") {
+            let rule_string = rule_string.trim();
+            rule = Some(
+                rule_string
+                    .parse()
+                    .map_err(|_| ParseLifeError::InvalidRule {
+                        rule: rule_string.to_owned(),
+                    })?,
+            );
+        } else if let Some(position) = line.strip_prefix("#P") {
+            block_origin = parse_coordinates(position)?;
+            block_line = line;
+            row = 0;
+        } else if !line.starts_with('#') {
+            // Any other `#` line is ignored
+            for (column, c) in line.chars().enumerate() {
+                match c {
+                    LIVE => live_cells.push(cell_position(&block_origin, row, column).ok_or_else(
+                        || ParseLifeError::InvalidCoordinates {
+                            line: block_line.to_owned(),
+                        },
+                    )?),
+                    DEAD => {}
+                    _ => return Err(ParseLifeError::InvalidChar { invalid_char: c }),
+                }
+            }
+            row += 1;
+        }
+    }
+
+    Ok(Pattern {
+        grid: grid_from_live_cells(live_cells)?,
+        name: None,
+        comments,
+        rule,
+    })
+}
+
+/// It writes a pattern in the Life 1.05 format.
+///
+/// The pattern name and comments are written as `#D` lines and all the
+/// cells go in one block centered at (0,0). Conway's rule is written as
+/// `#N`, any other rule as `#R` in S/B notation.
+#[must_use]
+pub fn write_life_105(pattern: &Pattern) -> String {
+    let mut output = String::new();
+    writeln!(output, "{LIFE_105_HEADER}").unwrap();
+
+    for description in pattern.name.iter().chain(&pattern.comments) {
+        writeln!(output, "#D {description}").unwrap();
+    }
+
+    match &pattern.rule {
+        Some(rule) if *rule != Rule::conway() => {
+            writeln!(output, "#R {}", survival_birth_notation(rule)).unwrap();
+        }
+        _ => writeln!(output, "#N").unwrap(),
+    }
+
+    let grid = &pattern.grid;
+    let center = Position::from(&Coordinates::new(grid.rows() / 2, grid.columns() / 2));
+    writeln!(output, "#P {} {}", -center.column, -center.row).unwrap();
+
+    for row in 0..grid.rows() {
+        let cells: String = (0..grid.columns())
+            .map(|column| {
+                if grid.is_live(&Coordinates::new(row, column)) {
+                    LIVE
+                } else {
+                    DEAD
+                }
+            })
+            .collect();
+        // Empty lines are skipped when reading, so empty rows keep one dead cell
+        match cells.trim_end_matches(DEAD) {
+            "" => writeln!(output, "{DEAD}").unwrap(),
+            cells => writeln!(output, "{cells}").unwrap(),
+        }
+    }
+
+    output
+}
+
+fn lines_after_header<'a>(
+    text: &'a str,
+    header: &str,
+) -> Result<impl Iterator<Item = &'a str>, ParseLifeError> {
+    let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+
+    match lines.next() {
+        Some(line) if line.eq_ignore_ascii_case(header) => Ok(lines),
+        _ => Err(ParseLifeError::MissingHeader),
+    }
+}
+
+/// Position for an `x y` line, where `x` is the column and `y` is the row.
+fn parse_coordinates(line: &str) -> Result<Position, ParseLifeError> {
+    let invalid_coordinates = || ParseLifeError::InvalidCoordinates {
+        line: line.trim().to_owned(),
+    };
+
+    let values = line
+        .split_whitespace()
+        .map(str::parse::<i64>)
+        .collect::<Result<Vec<i64>, _>>()
+        .map_err(|_| invalid_coordinates())?;
+
+    match values[..] {
+        [column, row] => Ok(Position::new(row, column)),
+        _ => Err(invalid_coordinates()),
+    }
+}
+
+/// Position of the cell in the given row and column of a block, if it does
+/// not overflow.
+fn cell_position(block_origin: &Position, row: usize, column: usize) -> Option<Position> {
+    Some(Position::new(
+        block_origin.row.checked_add(i64::try_from(row).ok()?)?,
+        block_origin
+            .column
+            .checked_add(i64::try_from(column).ok()?)?,
+    ))
+}
+
+/// Grid with the size of the live cells bounding box, so that negative
+/// positions are moved into the grid.
+fn grid_from_live_cells(live_cells: Vec<Position>) -> Result<Grid, ParseLifeError> {
+    let universe = Universe::with_live_cells(live_cells);
+    let Some((left_top, right_bottom)) = universe.bounding_box() else {
+        return Ok(Grid::new_empty());
+    };
+
+    // The size is checked before the grid is built, big grids do not fit in memory
+    let rows = right_bottom.row.abs_diff(left_top.row).saturating_add(1);
+    let columns = right_bottom
+        .column
+        .abs_diff(left_top.column)
+        .saturating_add(1);
+    if rows
+        .checked_mul(columns)
+        .is_none_or(|cells| cells > MAX_CELLS as u64)
+    {
+        return Err(ParseLifeError::TooBig { rows, columns });
+    }
+
+    // Both fit in a `usize`, their product is at most `MAX_CELLS`
+    let offset = |from: i64, to: i64| {
+        usize::try_from(to.abs_diff(from)).expect("the offset should be inside the grid")
+    };
+    let mut cells = BitRows::new(
+        offset(left_top.row, right_bottom.row) + 1,
+        offset(left_top.column, right_bottom.column) + 1,
+    );
+    for position in universe.live_cells() {
+        cells.set(
+            offset(left_top.row, position.row),
+            offset(left_top.column, position.column),
+            true,
+        );
+    }
+
+    Ok(Grid::with_cells(cells))
+}
+
+/// Rule-string with the survival counts first, for example `23/3`.
+fn survival_birth_notation(rule: &Rule) -> String {
+    let counts = |enabled: &dyn Fn(usize) -> bool| -> String {
+        (0..=MAX_NEIGHBORS)
+            .filter(|count| enabled(*count))
+            .map(|count| count.to_string())
+            .collect()
+    };
+
+    format!(
+        "{}/{}",
+        counts(&|count| rule.survives(count)),
+        counts(&|count| rule.is_born(count))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::domain::{
+        fixtures::glider,
+        grid::Grid,
+        pattern::{
+            life::{
+                parse_life_105, parse_life_106, write_life_105, write_life_106, ParseLifeError,
+            },
+            Pattern,
+        },
+        rule::Rule,
+    };
+
+    #[test]
+    fn it_should_parse_a_life_106_pattern() {
+        let pattern = parse_life_106("#Life 1.06\n1 0\n2 1\n0 2\n1 2\n2 2").unwrap();
+
+        assert_eq!(pattern.grid, glider());
+    }
+
+    #[test]
+    fn negative_life_106_coordinates_are_moved_into_the_grid() {
+        let pattern = parse_life_106("#Life 1.06\n0 -1\n1 0\n-1 1\n0 1\n1 1").unwrap();
+
+        assert_eq!(pattern.grid, glider());
+    }
+
+    #[test]
+    fn it_should_fail_parsing_invalid_life_106_coordinates() {
+        assert_eq!(
+            parse_life_106("#Life 1.06\n1 0\n2"),
+            Err(ParseLifeError::InvalidCoordinates {
+                line: "2".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_fail_parsing_a_life_106_pattern_without_header() {
+        assert_eq!(
+            parse_life_106("1 0\n2 1"),
+            Err(ParseLifeError::MissingHeader)
+        );
+    }
+
+    #[test]
+    fn it_should_write_a_life_106_pattern() {
+        assert_eq!(
+            write_life_106(&Pattern::from(glider())),
+            "#Life 1.06\n1 0\n2 1\n0 2\n1 2\n2 2\n"
+        );
+    }
+
+    #[test]
+    fn it_should_parse_a_life_105_pattern() {
+        let pattern = parse_life_105(
+            "#Life 1.05
+             #D The smallest spaceship
+             #R 23/36
+             #P -1 -1
+             .*
+             ..*
+             ***",
+        )
+        .unwrap();
+
+        assert_eq!(pattern.grid, glider());
+        assert_eq!(pattern.comments, vec!["The smallest spaceship"]);
+        assert_eq!(pattern.rule, Some("B36/S23".parse().unwrap()));
+    }
+
+    #[test]
+    fn it_should_join_several_life_105_blocks() {
+        let pattern = parse_life_105(
+            "#Life 1.05
+             #N
+             #P 0 -2
+             *
+             #P -1 0
+             ***",
+        )
+        .unwrap();
+
+        assert_eq!(
+            pattern.grid,
+            Grid::from_str(
+                "⬛⬜⬛
+                 ⬛⬛⬛
+                 ⬜⬜⬜",
+            )
+            .unwrap()
+        );
+        assert_eq!(pattern.rule, Some(Rule::conway()));
+    }
+
+    #[test]
+    fn it_should_fail_parsing_an_invalid_char_in_a_life_105_pattern() {
+        assert_eq!(
+            parse_life_105("#Life 1.05\n#P 0 0\n.O."),
+            Err(ParseLifeError::InvalidChar { invalid_char: 'O' })
+        );
+    }
+
+    #[test]
+    fn it_should_write_a_life_105_pattern() {
+        let pattern = Pattern {
+            comments: vec!["The smallest spaceship".to_owned()],
+            ..Pattern::from(glider())
+        };
+
+        assert_eq!(
+            write_life_105(&pattern),
+            "#Life 1.05\n#D The smallest spaceship\n#N\n#P -1 -1\n.*\n..*\n***\n"
+        );
+    }
+
+    #[test]
+    fn it_should_read_what_it_writes_in_life_105() {
+        let pattern = Pattern {
+            comments: vec!["The smallest spaceship".to_owned()],
+            rule: Some("B36/S23".parse().unwrap()),
+            ..Pattern::from(glider())
+        };
+
+        assert_eq!(parse_life_105(&write_life_105(&pattern)).unwrap(), pattern);
+    }
+
+    #[test]
+    fn empty_rows_are_kept_in_life_105_patterns() {
+        let grid = Grid::from_str(
+            "⬜⬛
+             ⬛⬛
+             ⬛⬜",
+        )
+        .unwrap();
+
+        let life_105 = write_life_105(&Pattern::from(grid.clone()));
+
+        assert_eq!(parse_life_105(&life_105).unwrap().grid, grid);
+    }
+
+    #[test]
+    fn it_should_fail_parsing_life_106_cells_too_far_apart() {
+        assert_eq!(
+            parse_life_106("#Life 1.06\n0 0\n100000 100000"),
+            Err(ParseLifeError::TooBig {
+                rows: 100_001,
+                columns: 100_001
+            })
+        );
+        assert_eq!(
+            parse_life_106(&format!("#Life 1.06\n{} 0\n{} 0", i64::MIN, i64::MAX)),
+            Err(ParseLifeError::TooBig {
+                rows: 1,
+                columns: u64::MAX
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_fail_parsing_a_life_105_block_whose_cells_overflow() {
+        let line = format!("#P {} 0", i64::MAX);
+
+        assert_eq!(
+            parse_life_105(&format!("#Life 1.05\n{line}\n.*")),
+            Err(ParseLifeError::InvalidCoordinates { line })
+        );
+    }
+}
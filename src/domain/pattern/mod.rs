@@ -0,0 +1,295 @@
+pub mod cells;
+pub mod life;
+pub mod rle;
+
+use std::{error::Error, fmt, path::Path};
+
+use crate::domain::{
+    cell::alphabet::Alphabet,
+    grid::{Grid, ParseGridError},
+    rule::Rule,
+};
+
+use self::{
+    cells::{parse_cells, write_cells, ParseCellsError},
+    life::{
+        parse_life_105, parse_life_106, write_life_105, write_life_106, ParseLifeError,
+        LIFE_105_HEADER, LIFE_106_HEADER,
+    },
+    rle::{parse_rle, write_rle, ParseRleError},
+};
+
+/// A pattern loaded from a file.
+///
+/// Besides the cells, some formats carry metadata like the pattern name,
+/// free text comments or the rule the pattern was designed for.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Pattern {
+    pub grid: Grid,
+    pub name: Option<String>,
+    pub comments: Vec<String>,
+    pub rule: Option<Rule>,
+}
+
+impl From<Grid> for Pattern {
+    fn from(grid: Grid) -> Self {
+        Self {
+            grid,
+            ..Self::default()
+        }
+    }
+}
+
+/// Supported pattern file formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatternFormat {
+    /// The project's own format with one char per cell, ⬜/⬛ by default.
+    Text,
+    /// Plaintext: `!` comment lines and rows like `.O.` with `O` for live cells.
+    Cells,
+    /// Run Length Encoded: `x = 3, y = 3, rule = B3/S23` followed by `bo$2bo$3o!`.
+    Rle,
+    /// Life 1.05: `#P x y` blocks of `*` and `.` rows with relative offsets.
+    Life105,
+    /// Life 1.06: one `x y` line for every live cell.
+    Life106,
+}
+
+impl PatternFormat {
+    /// Format for the extension of the given file name, if it is a known one.
+    ///
+    /// Life 1.05 and Life 1.06 files share the `.lif` and `.life`
+    /// extensions, so their format is only known from the content.
+    #[must_use]
+    pub fn from_file_name(file_name: &str) -> Option<Self> {
+        let extension = Path::new(file_name).extension()?.to_str()?;
+
+        match extension.to_lowercase().as_str() {
+            "txt" => Some(PatternFormat::Text),
+            "cells" => Some(PatternFormat::Cells),
+            "rle" => Some(PatternFormat::Rle),
+            _ => None,
+        }
+    }
+
+    /// It guesses the format from the pattern file content.
+    #[must_use]
+    pub fn detect(text: &str) -> Self {
+        let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+
+        match lines.clone().next() {
+            Some(line) if line.starts_with('!') => return PatternFormat::Cells,
+            Some(line) if line.eq_ignore_ascii_case(LIFE_105_HEADER) => {
+                return PatternFormat::Life105
+            }
+            Some(line) if line.eq_ignore_ascii_case(LIFE_106_HEADER) => {
+                return PatternFormat::Life106
+            }
+            _ => {}
+        }
+
+        match lines.find(|line| !line.starts_with('#')) {
+            Some(line) if rle::is_header(line) => PatternFormat::Rle,
+            _ => PatternFormat::Text,
+        }
+    }
+
+    /// Format for the extension of the given file name or, when it is not
+    /// a known one, the format guessed from the content.
+    #[must_use]
+    pub fn of_file(file_name: &str, text: &str) -> Self {
+        Self::from_file_name(file_name).unwrap_or_else(|| Self::detect(text))
+    }
+
+    /// Format to save a pattern to the given file, with `text` being its
+    /// current content, if any.
+    ///
+    /// `.lif` and `.life` files are saved as Life 1.06, unless they already
+    /// hold a Life 1.05 pattern. Files with other unknown extensions have
+    /// no format to be saved in.
+    #[must_use]
+    pub fn for_saving(file_name: &str, text: &str) -> Option<Self> {
+        let extension = Path::new(file_name).extension()?.to_str()?;
+
+        match extension.to_lowercase().as_str() {
+            "lif" | "life" if Self::detect(text) == PatternFormat::Life105 => {
+                Some(PatternFormat::Life105)
+            }
+            "lif" | "life" => Some(PatternFormat::Life106),
+            _ => Self::from_file_name(file_name),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParsePatternError {
+    Text(ParseGridError),
+    Cells(ParseCellsError),
+    Rle(ParseRleError),
+    Life(ParseLifeError),
+}
+
+impl Error for ParsePatternError {}
+
+impl fmt::Display for ParsePatternError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParsePatternError::Text(error) => write!(f, "{error}"),
+            ParsePatternError::Cells(error) => write!(f, "{error}"),
+            ParsePatternError::Rle(error) => write!(f, "{error}"),
+            ParsePatternError::Life(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+/// The alphabet is only used by the text format, the other formats have
+/// their own chars for the cells.
+///
+/// # Errors
+///
+/// Will return `Err` if the text is not a valid pattern in the given format.
+pub fn parse_pattern(
+    text: &str,
+    format: PatternFormat,
+    alphabet: &Alphabet,
+) -> Result<Pattern, ParsePatternError> {
+    match format {
+        PatternFormat::Text => Grid::parse_with(text, alphabet)
+            .map(Pattern::from)
+            .map_err(ParsePatternError::Text),
+        PatternFormat::Cells => parse_cells(text).map_err(ParsePatternError::Cells),
+        PatternFormat::Rle => parse_rle(text).map_err(ParsePatternError::Rle),
+        PatternFormat::Life105 => parse_life_105(text).map_err(ParsePatternError::Life),
+        PatternFormat::Life106 => parse_life_106(text).map_err(ParsePatternError::Life),
+    }
+}
+
+/// It writes the pattern in the given format. Formats without metadata
+/// only keep the cells.
+#[must_use]
+pub fn write_pattern(pattern: &Pattern, format: PatternFormat, alphabet: &Alphabet) -> String {
+    match format {
+        PatternFormat::Text => pattern.grid.to_string_with(alphabet),
+        PatternFormat::Cells => write_cells(pattern),
+        PatternFormat::Rle => write_rle(pattern),
+        PatternFormat::Life105 => write_life_105(pattern),
+        PatternFormat::Life106 => write_life_106(pattern),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::{
+        cell::alphabet::Alphabet,
+        pattern::{parse_pattern, write_pattern, PatternFormat},
+    };
+
+    #[test]
+    fn it_should_pick_the_format_from_the_file_extension() {
+        assert_eq!(
+            PatternFormat::from_file_name("./patterns/glider.RLE"),
+            Some(PatternFormat::Rle)
+        );
+        assert_eq!(
+            PatternFormat::from_file_name("glider.txt"),
+            Some(PatternFormat::Text)
+        );
+        assert_eq!(
+            PatternFormat::from_file_name("glider.cells"),
+            Some(PatternFormat::Cells)
+        );
+        assert_eq!(PatternFormat::from_file_name("glider"), None);
+    }
+
+    #[test]
+    fn it_should_detect_the_format_from_the_content() {
+        assert_eq!(
+            PatternFormat::detect("#N Glider\nx = 3, y = 3\nbo$2bo$3o!"),
+            PatternFormat::Rle
+        );
+        assert_eq!(
+            PatternFormat::detect("!Name: Glider\n.O.\n..O\nOOO"),
+            PatternFormat::Cells
+        );
+        assert_eq!(
+            PatternFormat::detect("#Life 1.05\n#P -1 -1\n.*\n..*\n***"),
+            PatternFormat::Life105
+        );
+        assert_eq!(
+            PatternFormat::detect("#Life 1.06\n1 0\n2 1\n0 2\n1 2\n2 2"),
+            PatternFormat::Life106
+        );
+        assert_eq!(PatternFormat::detect("⬛⬜⬛\n⬛⬛⬜"), PatternFormat::Text);
+    }
+
+    #[test]
+    fn the_file_extension_has_priority_over_the_content() {
+        assert_eq!(
+            PatternFormat::of_file("glider.txt", "x = 3, y = 3\nbo$2bo$3o!"),
+            PatternFormat::Text
+        );
+        assert_eq!(
+            PatternFormat::of_file("glider.life", "x = 3, y = 3\nbo$2bo$3o!"),
+            PatternFormat::Rle
+        );
+    }
+
+    #[test]
+    fn life_files_should_be_saved_as_life_106_unless_they_are_life_105() {
+        assert_eq!(
+            PatternFormat::for_saving("glider.life", ""),
+            Some(PatternFormat::Life106)
+        );
+        assert_eq!(
+            PatternFormat::for_saving("glider.LIF", "#Life 1.05\n#P -1 -1\n.*\n..*\n***"),
+            Some(PatternFormat::Life105)
+        );
+        assert_eq!(
+            PatternFormat::for_saving("glider.rle", "⬛⬜⬛\n⬛⬛⬜"),
+            Some(PatternFormat::Rle)
+        );
+    }
+
+    #[test]
+    fn patterns_should_not_be_saved_with_an_unknown_extension() {
+        assert_eq!(
+            PatternFormat::for_saving("glider", "x = 3, y = 3\nbo$2bo$3o!"),
+            None
+        );
+        assert_eq!(PatternFormat::for_saving("glider.png", ""), None);
+    }
+
+    #[test]
+    fn it_should_convert_a_pattern_between_formats() {
+        let alphabet = Alphabet::default();
+        let text = "⬛⬜⬛\n⬛⬛⬜\n⬜⬜⬜\n";
+
+        let pattern = parse_pattern(text, PatternFormat::Text, &alphabet).unwrap();
+        let rle = write_pattern(&pattern, PatternFormat::Rle, &alphabet);
+        let pattern = parse_pattern(&rle, PatternFormat::Rle, &alphabet).unwrap();
+        let cells = write_pattern(&pattern, PatternFormat::Cells, &alphabet);
+        let pattern = parse_pattern(&cells, PatternFormat::Cells, &alphabet).unwrap();
+        let life_105 = write_pattern(&pattern, PatternFormat::Life105, &alphabet);
+        let pattern = parse_pattern(&life_105, PatternFormat::Life105, &alphabet).unwrap();
+        let life_106 = write_pattern(&pattern, PatternFormat::Life106, &alphabet);
+        let pattern = parse_pattern(&life_106, PatternFormat::Life106, &alphabet).unwrap();
+
+        assert_eq!(
+            write_pattern(&pattern, PatternFormat::Text, &alphabet),
+            text
+        );
+    }
+
+    #[test]
+    fn text_patterns_use_the_given_alphabet() {
+        let alphabet = Alphabet::new('#', '.');
+
+        let pattern = parse_pattern(".#.\n..#\n###", PatternFormat::Text, &alphabet).unwrap();
+
+        assert_eq!(pattern.grid.population(), 5);
+        assert_eq!(
+            write_pattern(&pattern, PatternFormat::Text, &alphabet),
+            ".#.\n..#\n###\n"
+        );
+    }
+}
@@ -0,0 +1,517 @@
+use std::{error::Error, fmt, fmt::Write};
+
+use crate::domain::{
+    grid::{bits::BitRows, Grid},
+    rule::Rule,
+};
+
+use super::Pattern;
+
+const DEAD: char = 'b';
+const LIVE: char = 'o';
+const END_OF_ROW: char = '$';
+const END_OF_PATTERN: char = '!';
+
+/// Maximum length of the lines written, as recommended by the format.
+const MAX_LINE_LENGTH: usize = 70;
+
+/// Biggest pattern read, in cells, so that a file cannot take all the
+/// memory with a huge size in its header.
+pub const MAX_CELLS: usize = 1 << 28;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseRleError {
+    /// There is no `x = ..., y = ...` line before the cells.
+    MissingHeader,
+    /// The header line is not `x = <columns>, y = <rows>[, rule = <rule>]`.
+    InvalidHeader { header: String },
+    /// The rule in the header is not a valid rule-string.
+    InvalidRule { rule: String },
+    /// The pattern body contains a char which is not a run count or a tag.
+    InvalidChar { invalid_char: char },
+    /// The header size is bigger than `MAX_CELLS`.
+    TooBig { rows: usize, columns: usize },
+    /// A run count does not fit in the size given in the header.
+    RunOutsideSize { rows: usize, columns: usize },
+}
+
+impl Error for ParseRleError {}
+
+impl fmt::Display for ParseRleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseRleError::MissingHeader => write!(f, "Missing RLE header line"),
+            ParseRleError::InvalidHeader { header } => {
+                write!(f, "Invalid RLE header line '{header}'")
+            }
+            ParseRleError::InvalidRule { rule } => write!(f, "Invalid rule '{rule}' in RLE header"),
+            ParseRleError::InvalidChar { invalid_char } => {
+                write!(f, "Invalid char '{invalid_char}' for RLE pattern")
+            }
+            ParseRleError::TooBig { rows, columns } => write!(
+                f,
+                "RLE pattern of {columns}x{rows} cells is bigger than the limit of {MAX_CELLS} cells"
+            ),
+            ParseRleError::RunOutsideSize { rows, columns } => write!(
+                f,
+                "Run of cells outside the {columns}x{rows} size in the RLE header"
+            ),
+        }
+    }
+}
+
+struct Header {
+    rows: usize,
+    columns: usize,
+    rule: Option<Rule>,
+}
+
+/// True if the line looks like an RLE header (`x = 3, y = 3`).
+#[must_use]
+pub fn is_header(line: &str) -> bool {
+    line.split_once('=')
+        .is_some_and(|(name, _)| name.trim().eq_ignore_ascii_case("x"))
+}
+
+/// It parses a pattern in the Run Length Encoded format.
+///
+/// `#N` lines give the pattern name and `#C` (or `#c`) lines comments.
+/// Any other `#` line is ignored. Then comes the header with the pattern
+/// size and optionally the rule, and the cells: runs of dead (`b`) or live
+/// (`o`) cells, with `$` at the end of each row and `!` at the end of the
+/// pattern. Rows can be split across several lines. The cells must fit in
+/// the size given in the header.
+///
+/// # Errors
+///
+/// Will return `Err` if the header is missing or invalid, if the pattern is
+/// bigger than `MAX_CELLS` or if the body contains an invalid char or runs
+/// outside the pattern size.
+pub fn parse_rle(text: &str) -> Result<Pattern, ParseRleError> {
+    let mut name = None;
+    let mut comments = vec![];
+    let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+
+    let header = loop {
+        let line = lines.next().ok_or(ParseRleError::MissingHeader)?;
+
+        if let Some(comment) = line.strip_prefix('#') {
+            let (tag, content) = comment.split_at(comment.chars().next().map_or(0, char::len_utf8));
+            match tag {
+                "N" => name = Some(content.trim().to_owned()),
+                "C" | "c" => comments.push(content.trim().to_owned()),
+                _ => {}
+            }
+        } else if is_header(line) {
+            break parse_header(line)?;
+        } else {
+            return Err(ParseRleError::MissingHeader);
+        }
+    };
+
+    let live_cells = parse_body(lines, &header)?;
+
+    let (rows, columns) = (header.rows, header.columns);
+    let mut cells = BitRows::new(rows, if rows == 0 { 0 } else { columns });
+    for (row, column) in live_cells {
+        cells.set(row, column, true);
+    }
+
+    Ok(Pattern {
+        grid: Grid::with_cells(cells),
+        name,
+        comments,
+        rule: header.rule,
+    })
+}
+
+fn parse_header(line: &str) -> Result<Header, ParseRleError> {
+    let invalid_header = || ParseRleError::InvalidHeader {
+        header: line.to_owned(),
+    };
+
+    let mut header = Header {
+        rows: 0,
+        columns: 0,
+        rule: None,
+    };
+
+    for field in line.split(',') {
+        let (name, value) = field.split_once('=').ok_or_else(invalid_header)?;
+        let value = value.trim();
+
+        match name.trim().to_lowercase().as_str() {
+            "x" => header.columns = value.parse().map_err(|_| invalid_header())?,
+            "y" => header.rows = value.parse().map_err(|_| invalid_header())?,
+            "rule" => {
+                header.rule = Some(value.parse().map_err(|_| ParseRleError::InvalidRule {
+                    rule: value.to_owned(),
+                })?);
+            }
+            _ => return Err(invalid_header()),
+        }
+    }
+
+    if header
+        .rows
+        .checked_mul(header.columns)
+        .is_none_or(|cells| cells > MAX_CELLS)
+    {
+        return Err(ParseRleError::TooBig {
+            rows: header.rows,
+            columns: header.columns,
+        });
+    }
+
+    Ok(header)
+}
+
+/// Row and column of the live cells in the pattern body. Runs are checked
+/// against the header size before they are added.
+fn parse_body<'a, I: Iterator<Item = &'a str>>(
+    lines: I,
+    header: &Header,
+) -> Result<Vec<(usize, usize)>, ParseRleError> {
+    let outside_size = || ParseRleError::RunOutsideSize {
+        rows: header.rows,
+        columns: header.columns,
+    };
+    // End of the run, if it does not go past the given limit
+    let run_end = |start: usize, run: usize, limit: usize| {
+        start
+            .checked_add(run)
+            .filter(|end| *end <= limit)
+            .ok_or_else(outside_size)
+    };
+
+    let mut live_cells = vec![];
+    let (mut row, mut column) = (0, 0);
+    let mut run_count: Option<usize> = None;
+
+    'lines: for line in lines {
+        for c in line.chars() {
+            if let Some(digit) = c.to_digit(10) {
+                run_count = Some(
+                    run_count
+                        .unwrap_or(0)
+                        .checked_mul(10)
+                        .and_then(|run_count| run_count.checked_add(digit as usize))
+                        .ok_or_else(outside_size)?,
+                );
+                continue;
+            }
+
+            let run = run_count.take().unwrap_or(1);
+            match c {
+                DEAD | '.' => column = run_end(column, run, header.columns)?,
+                LIVE | 'A' => {
+                    if row >= header.rows {
+                        return Err(outside_size());
+                    }
+                    let end = run_end(column, run, header.columns)?;
+                    live_cells.extend((column..end).map(|column| (row, column)));
+                    column = end;
+                }
+                END_OF_ROW => {
+                    row = run_end(row, run, header.rows)?;
+                    column = 0;
+                }
+                END_OF_PATTERN => break 'lines,
+                c if c.is_whitespace() => {}
+                _ => return Err(ParseRleError::InvalidChar { invalid_char: c }),
+            }
+        }
+    }
+
+    Ok(live_cells)
+}
+
+/// It writes a pattern in the Run Length Encoded format.
+///
+/// Dead cells at the end of a row and empty rows at the end of the pattern
+/// are left out, as the header already gives the pattern size. When the
+/// pattern has no rule, the default one is written.
+#[must_use]
+pub fn write_rle(pattern: &Pattern) -> String {
+    let mut output = String::new();
+
+    if let Some(name) = &pattern.name {
+        writeln!(output, "#N {name}").unwrap();
+    }
+    for comment in &pattern.comments {
+        writeln!(output, "#C {comment}").unwrap();
+    }
+
+    let grid = &pattern.grid;
+    writeln!(
+        output,
+        "x = {}, y = {}, rule = {}",
+        grid.columns(),
+        grid.rows(),
+        pattern.rule.clone().unwrap_or_default()
+    )
+    .unwrap();
+
+    let mut line = String::new();
+    for token in body_tokens(grid) {
+        if line.len() + token.len() > MAX_LINE_LENGTH {
+            writeln!(output, "{line}").unwrap();
+            line.clear();
+        }
+        line.push_str(&token);
+    }
+    writeln!(output, "{line}").unwrap();
+
+    output
+}
+
+fn body_tokens(grid: &Grid) -> Vec<String> {
+    let mut tokens = vec![];
+    let mut pending_ends_of_row = 0;
+
+    for row in 0..grid.rows() {
+        if row > 0 {
+            pending_ends_of_row += 1;
+        }
+
+        let runs = runs_of(grid.cells(), row);
+        if runs.is_empty() {
+            continue;
+        }
+
+        if pending_ends_of_row > 0 {
+            tokens.push(token(pending_ends_of_row, END_OF_ROW));
+            pending_ends_of_row = 0;
+        }
+        tokens.extend(
+            runs.into_iter()
+                .map(|(length, live)| token(length, if live { LIVE } else { DEAD })),
+        );
+    }
+
+    tokens.push(END_OF_PATTERN.to_string());
+    tokens
+}
+
+/// Runs of cells with the same state, without the trailing dead cells.
+fn runs_of(cells: &BitRows, row: usize) -> Vec<(usize, bool)> {
+    let mut runs: Vec<(usize, bool)> = vec![];
+
+    for column in 0..cells.columns() {
+        let live = cells.get(row, column);
+        match runs.last_mut() {
+            Some((length, state)) if *state == live => *length += 1,
+            _ => runs.push((1, live)),
+        }
+    }
+
+    if runs.last().is_some_and(|(_, live)| !live) {
+        runs.pop();
+    }
+
+    runs
+}
+
+fn token(run: usize, tag: char) -> String {
+    if run == 1 {
+        tag.to_string()
+    } else {
+        format!("{run}{tag}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::domain::{
+        fixtures::glider,
+        grid::Grid,
+        pattern::{
+            rle::{parse_rle, write_rle, ParseRleError},
+            Pattern,
+        },
+        rule::Rule,
+    };
+
+    #[test]
+    fn it_should_parse_a_pattern() {
+        let pattern = parse_rle("x = 3, y = 3, rule = B3/S23\nbo$2bo$3o!").unwrap();
+
+        assert_eq!(pattern.grid, glider());
+        assert_eq!(pattern.rule, Some(Rule::conway()));
+    }
+
+    #[test]
+    fn it_should_parse_the_name_and_the_comments() {
+        let pattern = parse_rle(
+            "#N Glider
+             #O Richard K. Guy
+             #C The smallest spaceship
+             #c It moves diagonally
+             x = 3, y = 3
+             bo$2bo$3o!",
+        )
+        .unwrap();
+
+        assert_eq!(pattern.name, Some("Glider".to_owned()));
+        assert_eq!(
+            pattern.comments,
+            vec!["The smallest spaceship", "It moves diagonally"]
+        );
+        assert_eq!(pattern.rule, None);
+    }
+
+    #[test]
+    fn it_should_parse_a_body_split_across_several_lines() {
+        let pattern = parse_rle("x = 3, y = 3\nbo$2b\no$3\no!").unwrap();
+
+        assert_eq!(pattern.grid, glider());
+    }
+
+    #[test]
+    fn it_should_parse_run_counts_for_empty_rows() {
+        let pattern = parse_rle("x = 2, y = 3\no2$bo!").unwrap();
+
+        assert_eq!(
+            pattern.grid,
+            Grid::from_str(
+                "⬜⬛
+                 ⬛⬛
+                 ⬛⬜",
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn the_grid_has_the_size_given_in_the_header() {
+        let pattern = parse_rle("x = 4, y = 2\no!").unwrap();
+
+        assert_eq!(pattern.grid.rows(), 2);
+        assert_eq!(pattern.grid.columns(), 4);
+    }
+
+    #[test]
+    fn it_should_fail_without_a_header() {
+        assert_eq!(parse_rle("bo$2bo$3o!"), Err(ParseRleError::MissingHeader));
+    }
+
+    #[test]
+    fn it_should_fail_with_an_invalid_header() {
+        assert_eq!(
+            parse_rle("x = 3, y = three\nbo$2bo$3o!"),
+            Err(ParseRleError::InvalidHeader {
+                header: "x = 3, y = three".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_fail_with_an_invalid_rule() {
+        assert_eq!(
+            parse_rle("x = 3, y = 3, rule = B9/S23\nbo$2bo$3o!"),
+            Err(ParseRleError::InvalidRule {
+                rule: "B9/S23".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_fail_with_an_invalid_char_in_the_body() {
+        assert_eq!(
+            parse_rle("x = 3, y = 3\nbo$2bz$3o!"),
+            Err(ParseRleError::InvalidChar { invalid_char: 'z' })
+        );
+    }
+
+    #[test]
+    fn it_should_fail_with_a_run_count_too_big_for_the_header_size() {
+        let outside_size = Err(ParseRleError::RunOutsideSize {
+            rows: 3,
+            columns: 3,
+        });
+
+        assert_eq!(
+            parse_rle("x = 3, y = 3\n99999999999999999999999o!"),
+            outside_size
+        );
+        assert_eq!(parse_rle("x = 3, y = 3\nbo$4o!"), outside_size);
+        assert_eq!(parse_rle("x = 3, y = 3\n4$o!"), outside_size);
+        assert_eq!(parse_rle("x = 3, y = 3\n3$o!"), outside_size);
+    }
+
+    #[test]
+    fn it_should_fail_with_a_header_size_too_big_to_fit_in_memory() {
+        assert_eq!(
+            parse_rle("x = 100000, y = 100000\no!"),
+            Err(ParseRleError::TooBig {
+                rows: 100_000,
+                columns: 100_000
+            })
+        );
+        assert_eq!(
+            parse_rle("x = 18446744073709551615, y = 2\no!"),
+            Err(ParseRleError::TooBig {
+                rows: 2,
+                columns: usize::MAX
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_write_a_pattern() {
+        let pattern = Pattern {
+            grid: glider(),
+            name: Some("Glider".to_owned()),
+            comments: vec!["The smallest spaceship".to_owned()],
+            rule: None,
+        };
+
+        assert_eq!(
+            write_rle(&pattern),
+            "#N Glider\n#C The smallest spaceship\nx = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n"
+        );
+    }
+
+    #[test]
+    fn it_should_write_run_counts_for_empty_rows() {
+        let grid = Grid::from_str(
+            "⬛⬛
+             ⬜⬛
+             ⬛⬛
+             ⬛⬛
+             ⬛⬜
+             ⬛⬛",
+        )
+        .unwrap();
+
+        assert_eq!(
+            write_rle(&Pattern::from(grid)),
+            "x = 2, y = 6, rule = B3/S23\n$o3$bo!\n"
+        );
+    }
+
+    #[test]
+    fn it_should_split_long_lines() {
+        let row = "⬜⬛".repeat(50);
+        let grid = Grid::from_str(&row).unwrap();
+
+        let rle = write_rle(&Pattern::from(grid.clone()));
+
+        assert!(rle.lines().all(|line| line.len() <= 70));
+        assert_eq!(parse_rle(&rle).unwrap().grid, grid);
+    }
+
+    #[test]
+    fn it_should_read_what_it_writes() {
+        let pattern = Pattern {
+            grid: glider(),
+            name: Some("Glider".to_owned()),
+            comments: vec![],
+            rule: Some("B36/S23".parse().unwrap()),
+        };
+
+        assert_eq!(parse_rle(&write_rle(&pattern)).unwrap(), pattern);
+    }
+}
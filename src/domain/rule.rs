@@ -0,0 +1,343 @@
+use std::{error::Error, fmt, str::FromStr};
+
+use super::cell::state::State;
+
+const MAX_NEIGHBORS: usize = 8;
+
+/// Outer-totalistic rule for Life-like cellular automata.
+///
+/// The next state of a cell only depends on its current state and the
+/// number of live neighbours it has. The rule contains the neighbour counts
+/// that make a dead cell to be born and the ones that make a live cell
+/// survive.
+///
+/// It can be parsed from the usual rule-string notations:
+///
+/// - B/S notation: `B3/S23` (Conway's Game of Life), `B36/S23` (`HighLife`).
+/// - S/B notation: `23/3` (survival digits first, then birth digits).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    birth: [bool; MAX_NEIGHBORS + 1],
+    survival: [bool; MAX_NEIGHBORS + 1],
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseRuleError {
+    /// The rule-string contains a char which is not allowed at its position.
+    InvalidChar { invalid_char: char },
+    /// The rule-string does not contain the `/` separating birth and survival.
+    MissingSeparator,
+    /// The list after the `/` does not start with the `B` or `S` letter
+    /// expected after the letter of the first list.
+    MissingPrefix { expected: char },
+}
+
+impl Error for ParseRuleError {}
+
+impl fmt::Display for ParseRuleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseRuleError::InvalidChar { invalid_char } => {
+                write!(f, "Invalid char '{invalid_char}' for rule-string")
+            }
+            ParseRuleError::MissingSeparator => {
+                write!(f, "Missing '/' separator in rule-string")
+            }
+            ParseRuleError::MissingPrefix { expected } => {
+                write!(f, "Missing '{expected}' after the '/' in rule-string")
+            }
+        }
+    }
+}
+
+/// Rules where a dead cell with no live neighbours is born (`B0`) can not be
+/// played on an unbounded universe, because they would fill it.
+#[derive(Debug, PartialEq, Eq)]
+pub struct UnboundedB0RuleError {
+    pub rule: Rule,
+}
+
+impl Error for UnboundedB0RuleError {}
+
+impl fmt::Display for UnboundedB0RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "The {} rule gives birth to dead cells with no live neighbours, which would fill an unbounded universe",
+            self.rule
+        )
+    }
+}
+
+impl Default for Rule {
+    fn default() -> Self {
+        Self::conway()
+    }
+}
+
+impl FromStr for Rule {
+    type Err = ParseRuleError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim();
+
+        let Some((first, second)) = text.split_once('/') else {
+            return Err(ParseRuleError::MissingSeparator);
+        };
+
+        match (first.chars().next(), second.chars().next()) {
+            (Some('B' | 'b'), _) => Ok(Self {
+                birth: parse_neighbor_counts(&first[1..])?,
+                survival: parse_neighbor_counts(strip_prefix(second, 'S')?)?,
+            }),
+            (Some('S' | 's'), _) => Ok(Self {
+                birth: parse_neighbor_counts(strip_prefix(second, 'B')?)?,
+                survival: parse_neighbor_counts(&first[1..])?,
+            }),
+            _ => Ok(Self {
+                birth: parse_neighbor_counts(second)?,
+                survival: parse_neighbor_counts(first)?,
+            }),
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "B{}/S{}",
+            display_neighbor_counts(&self.birth),
+            display_neighbor_counts(&self.survival)
+        )
+    }
+}
+
+impl Rule {
+    /// # Panics
+    ///
+    /// Will panic if any of the neighbour counts is greater than 8.
+    #[must_use]
+    pub fn new(birth: &[usize], survival: &[usize]) -> Self {
+        Self {
+            birth: neighbor_counts(birth),
+            survival: neighbor_counts(survival),
+        }
+    }
+
+    /// Game of Life standard: B3/S23.
+    #[must_use]
+    pub fn conway() -> Self {
+        Self::new(&[3], &[2, 3])
+    }
+
+    #[must_use]
+    pub fn is_born(&self, number_of_live_neighbors: usize) -> bool {
+        number_of_live_neighbors <= MAX_NEIGHBORS && self.birth[number_of_live_neighbors]
+    }
+
+    #[must_use]
+    pub fn survives(&self, number_of_live_neighbors: usize) -> bool {
+        number_of_live_neighbors <= MAX_NEIGHBORS && self.survival[number_of_live_neighbors]
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if dead cells with no live neighbours are born
+    /// (`B0`), because the rule would fill an unbounded universe.
+    pub fn check_unbounded(&self) -> Result<(), UnboundedB0RuleError> {
+        if self.is_born(0) {
+            return Err(UnboundedB0RuleError { rule: self.clone() });
+        }
+        Ok(())
+    }
+
+    /// State of a cell in the next generation.
+    #[must_use]
+    pub fn next_state(&self, state: &State, number_of_live_neighbors: usize) -> State {
+        let live = match state {
+            State::Live => self.survives(number_of_live_neighbors),
+            State::Dead => self.is_born(number_of_live_neighbors),
+        };
+
+        if live {
+            State::Live
+        } else {
+            State::Dead
+        }
+    }
+}
+
+fn neighbor_counts(counts: &[usize]) -> [bool; MAX_NEIGHBORS + 1] {
+    let mut result = [false; MAX_NEIGHBORS + 1];
+    for &count in counts {
+        assert!(
+            count <= MAX_NEIGHBORS,
+            "A cell can not have more than {MAX_NEIGHBORS} neighbours"
+        );
+        result[count] = true;
+    }
+    result
+}
+
+fn strip_prefix(text: &str, prefix: char) -> Result<&str, ParseRuleError> {
+    match text.chars().next() {
+        Some(c) if c.eq_ignore_ascii_case(&prefix) => Ok(&text[1..]),
+        Some(c) => Err(ParseRuleError::InvalidChar { invalid_char: c }),
+        None => Err(ParseRuleError::MissingPrefix { expected: prefix }),
+    }
+}
+
+fn parse_neighbor_counts(digits: &str) -> Result<[bool; MAX_NEIGHBORS + 1], ParseRuleError> {
+    let mut result = [false; MAX_NEIGHBORS + 1];
+    for c in digits.chars() {
+        match c.to_digit(10) {
+            Some(digit) if digit as usize <= MAX_NEIGHBORS => result[digit as usize] = true,
+            _ => return Err(ParseRuleError::InvalidChar { invalid_char: c }),
+        }
+    }
+    Ok(result)
+}
+
+fn display_neighbor_counts(counts: &[bool; MAX_NEIGHBORS + 1]) -> String {
+    counts
+        .iter()
+        .enumerate()
+        .filter(|(_, enabled)| **enabled)
+        .map(|(count, _)| count.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::{
+        cell::state::State,
+        rule::{ParseRuleError, Rule},
+    };
+
+    #[test]
+    fn the_default_rule_is_the_conway_game_of_life_rule() {
+        assert_eq!(Rule::default(), Rule::new(&[3], &[2, 3]));
+    }
+
+    #[test]
+    fn a_dead_cell_is_born_if_its_number_of_live_neighbors_is_in_the_birth_list() {
+        let rule = Rule::conway();
+
+        assert!(rule.is_born(3));
+        assert!(!rule.is_born(2));
+    }
+
+    #[test]
+    fn a_live_cell_survives_if_its_number_of_live_neighbors_is_in_the_survival_list() {
+        let rule = Rule::conway();
+
+        assert!(rule.survives(2));
+        assert!(rule.survives(3));
+        assert!(!rule.survives(4));
+    }
+
+    #[test]
+    fn it_should_calculate_the_next_cell_state() {
+        let rule = Rule::conway();
+
+        assert_eq!(rule.next_state(&State::Dead, 3), State::Live);
+        assert_eq!(rule.next_state(&State::Dead, 2), State::Dead);
+        assert_eq!(rule.next_state(&State::Live, 2), State::Live);
+        assert_eq!(rule.next_state(&State::Live, 1), State::Dead);
+    }
+
+    #[test]
+    #[should_panic(expected = "A cell can not have more than 8 neighbours")]
+    fn a_cell_can_not_have_more_than_eight_neighbors() {
+        let _ = Rule::new(&[9], &[]);
+    }
+
+    mod for_parsing {
+        use crate::domain::rule::{ParseRuleError, Rule};
+
+        #[test]
+        fn it_should_be_parsed_from_the_b_s_notation() {
+            assert_eq!("B3/S23".parse::<Rule>().unwrap(), Rule::conway());
+            assert_eq!(
+                "B36/S23".parse::<Rule>().unwrap(),
+                Rule::new(&[3, 6], &[2, 3])
+            );
+            assert_eq!(
+                "B3678/S34678".parse::<Rule>().unwrap(),
+                Rule::new(&[3, 6, 7, 8], &[3, 4, 6, 7, 8])
+            );
+        }
+
+        #[test]
+        fn it_should_be_parsed_from_the_s_b_notation() {
+            assert_eq!("23/3".parse::<Rule>().unwrap(), Rule::conway());
+            assert_eq!("S23/B3".parse::<Rule>().unwrap(), Rule::conway());
+        }
+
+        #[test]
+        fn it_should_ignore_the_case_of_the_birth_and_survival_letters() {
+            assert_eq!("b3/s23".parse::<Rule>().unwrap(), Rule::conway());
+        }
+
+        #[test]
+        fn it_should_allow_empty_birth_or_survival_lists() {
+            // Seeds
+            assert_eq!("B2/S".parse::<Rule>().unwrap(), Rule::new(&[2], &[]));
+        }
+
+        #[test]
+        fn it_should_fail_when_the_separator_is_missing() {
+            assert_eq!(
+                "B3S23".parse::<Rule>().unwrap_err(),
+                ParseRuleError::MissingSeparator
+            );
+        }
+
+        #[test]
+        fn it_should_fail_showing_the_invalid_char() {
+            assert_eq!(
+                "B39/S23".parse::<Rule>().unwrap_err(),
+                ParseRuleError::InvalidChar { invalid_char: '9' }
+            );
+            assert_eq!(
+                "B3/X23".parse::<Rule>().unwrap_err(),
+                ParseRuleError::InvalidChar { invalid_char: 'X' }
+            );
+            assert_eq!(
+                "23/3a".parse::<Rule>().unwrap_err(),
+                ParseRuleError::InvalidChar { invalid_char: 'a' }
+            );
+        }
+
+        #[test]
+        fn it_should_fail_showing_the_missing_letter_of_the_second_list() {
+            assert_eq!(
+                "B3/".parse::<Rule>().unwrap_err(),
+                ParseRuleError::MissingPrefix { expected: 'S' }
+            );
+            assert_eq!(
+                "S23/".parse::<Rule>().unwrap_err(),
+                ParseRuleError::MissingPrefix { expected: 'B' }
+            );
+        }
+    }
+
+    #[test]
+    fn it_should_be_displayed_using_the_b_s_notation() {
+        assert_eq!(Rule::new(&[3, 6], &[2, 3]).to_string(), "B36/S23");
+        assert_eq!("23/3".parse::<Rule>().unwrap().to_string(), "B3/S23");
+    }
+
+    #[test]
+    fn parse_errors_should_be_displayed() {
+        assert_eq!(
+            ParseRuleError::InvalidChar { invalid_char: '9' }.to_string(),
+            "Invalid char '9' for rule-string"
+        );
+        assert_eq!(
+            ParseRuleError::MissingPrefix { expected: 'S' }.to_string(),
+            "Missing 'S' after the '/' in rule-string"
+        );
+    }
+}
@@ -1,9 +1,29 @@
 use std::time::Duration;
 
-use super::grid::size::Size;
+use super::{
+    cell::alphabet::Alphabet,
+    grid::{placement::Placement, size::Size, topology::Topology},
+    output::render_mode::RenderMode,
+    rule::Rule,
+};
 
+// Every flag is a game option of its own
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Clone)]
 pub struct Settings {
     pub back_grid_size: Size,          // Background grid size
     pub generations: u32,              // Number of generations to run the game
     pub generation_lifetime: Duration, // Lifetime for a generation
+    pub rule: Rule,                    // Rule used to calculate the next generation
+    pub topology: Topology,            // How the background grid edges are stitched together
+    pub unbounded: bool, // Play on an unbounded universe using the background grid as the viewport
+    pub threads: usize,  // Number of threads used to calculate the next generation
+    pub alphabet: Alphabet, // Chars used to display the live and dead cells
+    pub placement: Placement, // Where the pattern is placed on the background grid
+    pub until_stable: bool, // Stop on extinction, a still life or an oscillation
+    pub render_mode: RenderMode, // How the grid cells are drawn
+    pub colour_by_age: bool, // Paint the live cells with a colour for their age
+    pub trail: bool,     // Paint the recently dead cells dimmed
+    pub status_line: bool, // Show the statistics of the generation under the grid
+    pub statistics: bool, // Collect the statistics of every generation in the outcome
 }
@@ -0,0 +1,170 @@
+use super::{
+    grid::{functions::next_generation::next_generation_in_parallel, topology::Topology, Grid},
+    rule::Rule,
+};
+
+/// A simulation on a grid that can be advanced one generation at a time.
+///
+/// Unlike `play`, it does not print anything or wait between generations,
+/// so the caller decides when to stop and what to do with every generation.
+///
+/// As an iterator, every call to `next` calculates and returns the next
+/// generation, until the generation number reaches `u32::MAX`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Simulation {
+    grid: Grid,
+    rule: Rule,
+    threads: usize,
+    generation: u32,
+}
+
+impl Simulation {
+    /// The grid is generation 0. Its topology is used to find the
+    /// neighbours of the cells on the edges.
+    #[must_use]
+    pub fn new(grid: Grid, rule: Rule) -> Self {
+        Self {
+            grid,
+            rule,
+            threads: 1,
+            generation: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn with_topology(mut self, topology: Topology) -> Self {
+        self.grid = self.grid.with_topology(topology);
+        self
+    }
+
+    /// Number of threads used to calculate the next generation.
+    #[must_use]
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads;
+        self
+    }
+
+    /// Current generation.
+    #[must_use]
+    pub fn grid(&self) -> &Grid {
+        &self.grid
+    }
+
+    #[must_use]
+    pub fn rule(&self) -> &Rule {
+        &self.rule
+    }
+
+    #[must_use]
+    pub fn topology(&self) -> Topology {
+        self.grid.topology()
+    }
+
+    /// Number of the current generation.
+    #[must_use]
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// It advances one generation and returns the new one.
+    ///
+    /// # Panics
+    ///
+    /// If the current generation is already `u32::MAX`.
+    pub fn step(&mut self) -> &Grid {
+        self.generation = self
+            .generation
+            .checked_add(1)
+            .expect("the generation number should fit in a u32");
+        self.grid = next_generation_in_parallel(&self.grid, &self.rule, self.threads);
+        &self.grid
+    }
+
+    /// It advances the given number of generations and returns the last one.
+    ///
+    /// # Panics
+    ///
+    /// If the generation number would go past `u32::MAX`.
+    pub fn step_n(&mut self, generations: u32) -> &Grid {
+        for _ in 0..generations {
+            self.step();
+        }
+        &self.grid
+    }
+}
+
+impl Iterator for Simulation {
+    type Item = Grid;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        (self.generation < u32::MAX).then(|| self.step().clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::{
+        cell::coordinates::Coordinates,
+        fixtures::glider,
+        grid::{functions::overlap::overlap, topology::Topology, Grid},
+        rule::Rule,
+        simulation::Simulation,
+    };
+
+    /// The glider is at the left top corner.
+    fn glider_on_a_6x6_grid() -> Grid {
+        overlap(
+            &Grid::of_dead_cells(6, 6),
+            &glider(),
+            &Coordinates::new(0, 0),
+        )
+    }
+
+    #[test]
+    fn it_should_advance_several_generations_at_once() {
+        let mut one_by_one = Simulation::new(glider_on_a_6x6_grid(), Rule::default());
+        for _ in 0..4 {
+            one_by_one.step();
+        }
+
+        let mut at_once = Simulation::new(glider_on_a_6x6_grid(), Rule::default());
+
+        assert_eq!(at_once.step_n(4), one_by_one.grid());
+        assert_eq!(at_once.generation(), 4);
+    }
+
+    #[test]
+    fn it_should_iterate_over_the_next_generations() {
+        let populations: Vec<usize> = Simulation::new(glider_on_a_6x6_grid(), Rule::default())
+            .with_topology(Topology::Plane)
+            .take(20)
+            .map(|grid| grid.population())
+            .collect();
+
+        // The glider moves to the bottom right corner and becomes a block
+        assert_eq!(populations[..4], [5, 5, 5, 5]);
+        assert_eq!(populations[19], 4);
+    }
+
+    #[test]
+    fn the_result_should_not_depend_on_the_number_of_threads() {
+        let sequential = Simulation::new(glider_on_a_6x6_grid(), Rule::default()).nth(9);
+        let parallel = Simulation::new(glider_on_a_6x6_grid(), Rule::default())
+            .with_threads(3)
+            .nth(9);
+
+        assert_eq!(sequential, parallel);
+    }
+
+    #[test]
+    fn the_iteration_should_end_at_the_last_generation_number() {
+        let mut simulation = Simulation {
+            generation: u32::MAX - 1,
+            ..Simulation::new(glider_on_a_6x6_grid(), Rule::default())
+        };
+
+        assert!(simulation.next().is_some());
+        assert_eq!(simulation.generation(), u32::MAX);
+        assert_eq!(simulation.next(), None);
+    }
+}
@@ -0,0 +1,169 @@
+use std::{
+    collections::{hash_map::DefaultHasher, VecDeque},
+    fmt,
+    hash::{Hash, Hasher},
+};
+
+/// Longest cycle detected. Longer oscillations are not detected.
+pub const MAX_PERIOD: usize = 64;
+
+/// Why the game stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    /// All the generations were played.
+    GenerationLimit,
+    /// There are no live cells left.
+    Extinction,
+    /// The generation is the same as the previous one.
+    StillLife,
+    /// The generation is the same as the one `period` generations ago.
+    Oscillation { period: usize },
+    /// The player quit the game.
+    Quit,
+}
+
+impl fmt::Display for StopReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StopReason::GenerationLimit => write!(f, "generation limit reached"),
+            StopReason::Extinction => write!(f, "extinction"),
+            StopReason::StillLife => write!(f, "still life"),
+            StopReason::Oscillation { period } => write!(f, "oscillation with period {period}"),
+            StopReason::Quit => write!(f, "quit by the player"),
+        }
+    }
+}
+
+/// It detects when the game becomes stable: extinction, a still life or
+/// an oscillation with a period up to `MAX_PERIOD`.
+///
+/// Generations are compared by their hash, so only the hashes of the last
+/// generations are kept in memory.
+#[derive(Debug, Default)]
+pub struct StabilityDetector {
+    fingerprints: VecDeque<u64>,
+}
+
+impl StabilityDetector {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// It records the next generation and returns the reason to stop if the
+    /// game is stable.
+    pub fn observe<G: Hash>(&mut self, generation: &G, population: usize) -> Option<StopReason> {
+        if population == 0 {
+            return Some(StopReason::Extinction);
+        }
+
+        let fingerprint = fingerprint(generation);
+
+        let period = self
+            .fingerprints
+            .iter()
+            .rev()
+            .position(|previous| *previous == fingerprint)
+            .map(|index| index + 1);
+
+        if self.fingerprints.len() == MAX_PERIOD {
+            self.fingerprints.pop_front();
+        }
+        self.fingerprints.push_back(fingerprint);
+
+        match period {
+            Some(1) => Some(StopReason::StillLife),
+            Some(period) => Some(StopReason::Oscillation { period }),
+            None => None,
+        }
+    }
+}
+
+fn fingerprint<G: Hash>(generation: &G) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    generation.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::domain::{
+        grid::{functions::next_generation::next_generation, Grid},
+        rule::Rule,
+        stability::{StabilityDetector, StopReason},
+    };
+
+    /// Generation at which the detector stops the game, and why.
+    fn run_until_stable(mut grid: Grid) -> (usize, StopReason) {
+        let mut detector = StabilityDetector::new();
+
+        for generation in 0..100 {
+            if let Some(stop_reason) = detector.observe(&grid, grid.population()) {
+                return (generation, stop_reason);
+            }
+            grid = next_generation(&grid, &Rule::default());
+        }
+
+        panic!("the grid should become stable");
+    }
+
+    #[test]
+    fn it_should_detect_the_extinction() {
+        let grid = Grid::from_str(
+            "⬛⬛⬛⬛
+             ⬛⬜⬛⬛
+             ⬛⬛⬛⬛
+             ⬛⬛⬛⬛",
+        )
+        .unwrap();
+
+        assert_eq!(run_until_stable(grid), (1, StopReason::Extinction));
+    }
+
+    #[test]
+    fn it_should_detect_a_still_life() {
+        let block = Grid::from_str(
+            "⬛⬛⬛⬛
+             ⬛⬜⬜⬛
+             ⬛⬜⬜⬛
+             ⬛⬛⬛⬛",
+        )
+        .unwrap();
+
+        assert_eq!(run_until_stable(block), (1, StopReason::StillLife));
+    }
+
+    #[test]
+    fn it_should_detect_an_oscillation() {
+        let blinker = Grid::from_str(
+            "⬛⬛⬛⬛⬛
+             ⬛⬛⬛⬛⬛
+             ⬛⬜⬜⬜⬛
+             ⬛⬛⬛⬛⬛
+             ⬛⬛⬛⬛⬛",
+        )
+        .unwrap();
+
+        assert_eq!(
+            run_until_stable(blinker),
+            (2, StopReason::Oscillation { period: 2 })
+        );
+    }
+
+    #[test]
+    fn a_glider_on_a_torus_is_a_long_oscillation() {
+        // It goes back to the same cells after moving around the whole 8x8 grid
+        let mut rows = vec!["⬛".repeat(8); 8];
+        rows[0] = "⬛⬜⬛⬛⬛⬛⬛⬛".to_owned();
+        rows[1] = "⬛⬛⬜⬛⬛⬛⬛⬛".to_owned();
+        rows[2] = "⬜⬜⬜⬛⬛⬛⬛⬛".to_owned();
+        let glider = Grid::from_str(&rows.join("\n")).unwrap();
+
+        assert_eq!(
+            run_until_stable(glider),
+            (32, StopReason::Oscillation { period: 32 })
+        );
+    }
+}
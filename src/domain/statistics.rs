@@ -0,0 +1,300 @@
+use std::{fmt::Write, path::Path};
+
+use super::grid::{bounding_box::BoundingBox, Grid};
+
+/// Chars of the status line, with the spaces after the summary. It is wide
+/// enough for big grids and populations.
+const STATUS_LINE_WIDTH: usize = 100;
+
+/// Metrics of a generation shown in the game. On an unbounded universe,
+/// they are the metrics of the part of the universe inside the viewport.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenerationStats {
+    /// The pattern is generation 0.
+    pub generation: u32,
+    pub live: usize,
+    /// Cells born since the previous generation.
+    pub births: usize,
+    /// Cells that died since the previous generation.
+    pub deaths: usize,
+    pub bounding_box: Option<BoundingBox>,
+    /// Fraction of the cells that are alive, from 0 to 1.
+    pub density: f64,
+}
+
+impl GenerationStats {
+    /// Births and deaths are counted against the previous generation. The
+    /// first generation, or one with a different size than the previous
+    /// one, has none.
+    #[must_use]
+    pub fn of(generation: u32, grid: &Grid, previous: Option<&Grid>) -> Self {
+        let live = grid.population();
+
+        let (births, deaths) = match previous {
+            Some(previous) if previous.has_same_dimensions(grid) => changes(previous, grid),
+            _ => (0, 0),
+        };
+
+        Self {
+            generation,
+            live,
+            births,
+            deaths,
+            bounding_box: BoundingBox::of(grid),
+            density: density(live, grid.number_of_cells()),
+        }
+    }
+
+    /// A summary to show under the grid. It is padded to the same width for
+    /// every generation, so that only the changed chars have to be redrawn.
+    #[must_use]
+    pub fn status_line(&self) -> String {
+        let bounding_box = match self.bounding_box {
+            Some(bounding_box) => format!(
+                "{}x{} at {},{}",
+                bounding_box.rows(),
+                bounding_box.columns(),
+                bounding_box.top,
+                bounding_box.left
+            ),
+            None => "none".to_owned(),
+        };
+
+        let status_line = format!(
+            "Generation {}  live {}  births {}  deaths {}  box {}  density {:.2}%",
+            self.generation,
+            self.live,
+            self.births,
+            self.deaths,
+            bounding_box,
+            self.density * 100.0
+        );
+
+        format!("{status_line:<STATUS_LINE_WIDTH$}")
+    }
+}
+
+/// Fraction of the cells that are alive, 0 for a grid without cells.
+// Counts above 2^52 cells lose some precision, which a fraction can afford
+#[allow(clippy::cast_precision_loss)]
+fn density(live: usize, cells: usize) -> f64 {
+    if cells == 0 {
+        0.0
+    } else {
+        live as f64 / cells as f64
+    }
+}
+
+/// Cells born and cells that died from one generation to the next one,
+/// comparing the words of their rows.
+fn changes(previous: &Grid, next: &Grid) -> (usize, usize) {
+    let mut births = 0;
+    let mut deaths = 0;
+
+    for row in 0..next.rows() {
+        for (before, after) in previous.cells().row(row).iter().zip(next.cells().row(row)) {
+            births += (after & !before).count_ones() as usize;
+            deaths += (before & !after).count_ones() as usize;
+        }
+    }
+
+    (births, deaths)
+}
+
+/// File formats for the statistics of all the generations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatisticsFormat {
+    Csv,
+    Json,
+}
+
+impl StatisticsFormat {
+    /// Format for the extension of the given file name, if it is a known one.
+    #[must_use]
+    pub fn from_file_name(file_name: &str) -> Option<Self> {
+        let extension = Path::new(file_name).extension()?.to_str()?;
+
+        match extension.to_lowercase().as_str() {
+            "csv" => Some(StatisticsFormat::Csv),
+            "json" => Some(StatisticsFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+/// It writes the statistics of every generation, one row or object per
+/// generation. A generation without live cells has an empty bounding box.
+#[must_use]
+pub fn write_statistics(statistics: &[GenerationStats], format: StatisticsFormat) -> String {
+    match format {
+        StatisticsFormat::Csv => write_csv(statistics),
+        StatisticsFormat::Json => write_json(statistics),
+    }
+}
+
+fn write_csv(statistics: &[GenerationStats]) -> String {
+    let mut output = "generation,live,births,deaths,top,left,bottom,right,density\n".to_owned();
+
+    for stats in statistics {
+        let bounding_box = stats.bounding_box.map_or_else(
+            || ",,,".to_owned(),
+            |bounding_box| {
+                format!(
+                    "{},{},{},{}",
+                    bounding_box.top, bounding_box.left, bounding_box.bottom, bounding_box.right
+                )
+            },
+        );
+
+        let _ = writeln!(
+            output,
+            "{},{},{},{},{},{:.6}",
+            stats.generation, stats.live, stats.births, stats.deaths, bounding_box, stats.density
+        );
+    }
+
+    output
+}
+
+fn write_json(statistics: &[GenerationStats]) -> String {
+    let objects: Vec<String> = statistics
+        .iter()
+        .map(|stats| {
+            let bounding_box = stats.bounding_box.map_or_else(
+                || "null".to_owned(),
+                |bounding_box| {
+                    format!(
+                        "{{\"top\": {}, \"left\": {}, \"bottom\": {}, \"right\": {}}}",
+                        bounding_box.top,
+                        bounding_box.left,
+                        bounding_box.bottom,
+                        bounding_box.right
+                    )
+                },
+            );
+
+            format!(
+                "  {{\"generation\": {}, \"live\": {}, \"births\": {}, \"deaths\": {}, \
+                 \"bounding_box\": {}, \"density\": {:.6}}}",
+                stats.generation,
+                stats.live,
+                stats.births,
+                stats.deaths,
+                bounding_box,
+                stats.density
+            )
+        })
+        .collect();
+
+    if objects.is_empty() {
+        return "[]\n".to_owned();
+    }
+
+    format!("[\n{}\n]\n", objects.join(",\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::domain::{
+        grid::{bounding_box::BoundingBox, Grid},
+        statistics::{write_statistics, GenerationStats, StatisticsFormat},
+    };
+
+    fn blinker_stats() -> Vec<GenerationStats> {
+        let horizontal = Grid::from_str(
+            "⬛⬛⬛
+             ⬜⬜⬜
+             ⬛⬛⬛",
+        )
+        .unwrap();
+        let vertical = Grid::from_str(
+            "⬛⬜⬛
+             ⬛⬜⬛
+             ⬛⬜⬛",
+        )
+        .unwrap();
+
+        vec![
+            GenerationStats::of(0, &horizontal, None),
+            GenerationStats::of(1, &vertical, Some(&horizontal)),
+        ]
+    }
+
+    #[test]
+    fn it_should_count_the_births_and_deaths_since_the_previous_generation() {
+        let stats = blinker_stats();
+
+        assert_eq!((stats[0].births, stats[0].deaths), (0, 0));
+        assert_eq!((stats[1].births, stats[1].deaths), (2, 2));
+        assert_eq!(stats[1].live, 3);
+        assert!((stats[1].density - 3.0 / 9.0).abs() < f64::EPSILON);
+        assert_eq!(
+            stats[1].bounding_box,
+            Some(BoundingBox {
+                top: 0,
+                left: 1,
+                bottom: 2,
+                right: 1
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_summarize_a_generation_in_a_status_line() {
+        assert_eq!(
+            blinker_stats()[1].status_line().trim_end(),
+            "Generation 1  live 3  births 2  deaths 2  box 3x1 at 0,1  density 33.33%"
+        );
+    }
+
+    #[test]
+    fn the_status_line_should_have_the_same_width_for_every_generation() {
+        let empty = GenerationStats::of(1000, &Grid::of_dead_cells(3, 3), None);
+
+        assert_eq!(
+            blinker_stats()[0].status_line().len(),
+            empty.status_line().len()
+        );
+    }
+
+    #[test]
+    fn it_should_be_exported_as_csv() {
+        let empty = GenerationStats::of(2, &Grid::of_dead_cells(3, 3), None);
+
+        assert_eq!(
+            write_statistics(
+                &[&blinker_stats()[..], &[empty]].concat(),
+                StatisticsFormat::Csv
+            ),
+            "generation,live,births,deaths,top,left,bottom,right,density\n\
+             0,3,0,0,1,0,1,2,0.333333\n\
+             1,3,2,2,0,1,2,1,0.333333\n\
+             2,0,0,0,,,,,0.000000\n"
+        );
+    }
+
+    #[test]
+    fn it_should_be_exported_as_json() {
+        assert_eq!(
+            write_statistics(&blinker_stats()[..1], StatisticsFormat::Json),
+            "[\n  {\"generation\": 0, \"live\": 3, \"births\": 0, \"deaths\": 0, \
+             \"bounding_box\": {\"top\": 1, \"left\": 0, \"bottom\": 1, \"right\": 2}, \
+             \"density\": 0.333333}\n]\n"
+        );
+    }
+
+    #[test]
+    fn the_format_should_be_the_one_for_the_file_extension() {
+        assert_eq!(
+            StatisticsFormat::from_file_name("stats.CSV"),
+            Some(StatisticsFormat::Csv)
+        );
+        assert_eq!(
+            StatisticsFormat::from_file_name("stats.json"),
+            Some(StatisticsFormat::Json)
+        );
+        assert_eq!(StatisticsFormat::from_file_name("stats.txt"), None);
+    }
+}
@@ -1,5 +1,73 @@
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 pub trait Timer {
     fn wait(&self, duration: Duration);
+
+    /// Current instant, used to measure how long a frame takes.
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    /// It waits what is left of the frame duration since the frame started,
+    /// so the time spent calculating and rendering a generation is not
+    /// added to the generation lifetime. It does not wait at all if the
+    /// frame already took longer.
+    fn wait_until_frame_end(&self, frame_start: Instant, frame_duration: Duration) {
+        let elapsed = self.now().saturating_duration_since(frame_start);
+        self.wait(frame_duration.saturating_sub(elapsed));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::RefCell,
+        time::{Duration, Instant},
+    };
+
+    use crate::domain::timer::Timer;
+
+    struct FakeTimer {
+        start: Instant,
+        elapsed: Duration,
+        waits: RefCell<Vec<Duration>>,
+    }
+
+    impl FakeTimer {
+        fn with_elapsed_time(elapsed: Duration) -> Self {
+            Self {
+                start: Instant::now(),
+                elapsed,
+                waits: RefCell::new(vec![]),
+            }
+        }
+    }
+
+    impl Timer for FakeTimer {
+        fn wait(&self, duration: Duration) {
+            self.waits.borrow_mut().push(duration);
+        }
+
+        fn now(&self) -> Instant {
+            self.start + self.elapsed
+        }
+    }
+
+    #[test]
+    fn it_should_only_wait_what_is_left_of_the_frame() {
+        let timer = FakeTimer::with_elapsed_time(Duration::from_millis(30));
+
+        timer.wait_until_frame_end(timer.start, Duration::from_millis(100));
+
+        assert_eq!(*timer.waits.borrow(), vec![Duration::from_millis(70)]);
+    }
+
+    #[test]
+    fn it_should_not_wait_when_the_frame_took_longer_than_its_duration() {
+        let timer = FakeTimer::with_elapsed_time(Duration::from_millis(150));
+
+        timer.wait_until_frame_end(timer.start, Duration::from_millis(100));
+
+        assert_eq!(*timer.waits.borrow(), vec![Duration::ZERO]);
+    }
 }
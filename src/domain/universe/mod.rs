@@ -0,0 +1,383 @@
+pub mod position;
+
+use std::{
+    collections::{HashMap, HashSet},
+    hash::{Hash, Hasher},
+};
+
+use crate::domain::{
+    cell::{coordinates::Coordinates, row::Row, state::State, Cell},
+    grid::{
+        ages::{Age, CellAges},
+        size::Size,
+        topology::Topology,
+        Grid,
+    },
+    rule::Rule,
+};
+
+use self::position::Position;
+
+/// Unbounded universe.
+///
+/// It only stores the positions of the live cells, so it can grow without
+/// limit and patterns never wrap around or hit an edge.
+///
+/// Rules where a dead cell with no live neighbours is born (`B0`) are not
+/// supported, because they would fill the whole infinite universe.
+#[derive(Debug, Default, Clone, Eq)]
+pub struct Universe {
+    live_cells: HashSet<Position>,
+    /// Age of the live cells and the recently dead ones, when it is tracked.
+    ages: Option<HashMap<Position, Age>>,
+}
+
+/// Generations a dead cell is remembered when the ages are tracked, well
+/// beyond any trail. Older dead cells look like the ones never born.
+const DEAD_CELL_MEMORY: u32 = 16;
+
+/// Universes with the same live cells are equal, whatever their ages.
+impl PartialEq for Universe {
+    fn eq(&self, other: &Self) -> bool {
+        self.live_cells == other.live_cells
+    }
+}
+
+/// Live cells are hashed in order, so equal universes have the same hash.
+impl Hash for Universe {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let mut live_cells: Vec<&Position> = self.live_cells.iter().collect();
+        live_cells.sort_unstable();
+        live_cells.hash(state);
+    }
+}
+
+impl From<&Grid> for Universe {
+    /// It places the grid left top corner at position (0,0).
+    fn from(grid: &Grid) -> Self {
+        Self::with_live_cells(
+            grid.iter()
+                .filter(|coordinates| grid.get_cell(coordinates).is_live())
+                .map(|coordinates| Position::from(&coordinates)),
+        )
+    }
+}
+
+impl Universe {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn with_live_cells<I: IntoIterator<Item = Position>>(live_cells: I) -> Self {
+        Self {
+            live_cells: live_cells.into_iter().collect(),
+            ages: None,
+        }
+    }
+
+    /// It returns the same universe tracking the age of its cells. The
+    /// live cells are 1 generation old.
+    #[must_use]
+    pub fn with_ages(mut self) -> Self {
+        self.ages = Some(
+            self.live_cells
+                .iter()
+                .map(|position| (*position, Age::Live(1)))
+                .collect(),
+        );
+        self
+    }
+
+    /// Age of the cell at the given position, if the ages are tracked.
+    #[must_use]
+    pub fn age(&self, position: &Position) -> Option<Age> {
+        self.ages
+            .as_ref()
+            .map(|ages| ages.get(position).copied().unwrap_or(Age::Unborn))
+    }
+
+    #[must_use]
+    pub fn is_live(&self, position: &Position) -> bool {
+        self.live_cells.contains(position)
+    }
+
+    /// Number of live cells.
+    #[must_use]
+    pub fn population(&self) -> usize {
+        self.live_cells.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.live_cells.is_empty()
+    }
+
+    pub fn live_cells(&self) -> impl Iterator<Item = &Position> {
+        self.live_cells.iter()
+    }
+
+    /// Left top and right bottom corners of the smallest rectangle
+    /// containing all the live cells, or `None` if there is no live cell.
+    #[must_use]
+    pub fn bounding_box(&self) -> Option<(Position, Position)> {
+        let rows = self.live_cells.iter().map(|position| position.row);
+        let columns = self.live_cells.iter().map(|position| position.column);
+
+        Some((
+            Position::new(rows.clone().min()?, columns.clone().min()?),
+            Position::new(rows.max()?, columns.max()?),
+        ))
+    }
+
+    /// Calculate the next generation of cells for the universe applying the given rule.
+    /// The ages, if they are tracked, are carried to the next generation.
+    #[must_use]
+    pub fn next_generation(&self, rule: &Rule) -> Self {
+        let mut next = Self::with_live_cells(
+            self.number_of_live_neighbors()
+                .into_iter()
+                .filter(|(position, number_of_live_neighbors)| {
+                    let state = if self.is_live(position) {
+                        State::Live
+                    } else {
+                        State::Dead
+                    };
+                    rule.next_state(&state, *number_of_live_neighbors) == State::Live
+                })
+                .map(|(position, _)| position),
+        );
+        next.ages = self.ages.as_ref().map(|ages| next.next_ages(ages));
+        next
+    }
+
+    /// Ages of this generation, from the ages of the previous one. Cells
+    /// dead for longer than the memory are forgotten.
+    fn next_ages(&self, previous_ages: &HashMap<Position, Age>) -> HashMap<Position, Age> {
+        let live_ages = self.live_cells.iter().map(|position| {
+            let age = match previous_ages.get(position) {
+                Some(Age::Live(generations)) => Age::Live(generations.saturating_add(1)),
+                _ => Age::Live(1),
+            };
+            (*position, age)
+        });
+
+        let dead_ages = previous_ages
+            .iter()
+            .filter(|(position, _)| !self.is_live(position))
+            .filter_map(|(position, age)| match age {
+                Age::Live(_) => Some((*position, Age::Dead(1))),
+                Age::Dead(generations) if *generations < DEAD_CELL_MEMORY => {
+                    Some((*position, Age::Dead(generations + 1)))
+                }
+                Age::Dead(_) | Age::Unborn => None,
+            });
+
+        live_ages.chain(dead_ages).collect()
+    }
+
+    /// It returns a window into the universe with the given size and the
+    /// left top corner at the given position.
+    #[must_use]
+    pub fn viewport(&self, origin: &Position, size: &Size) -> Grid {
+        let cell_rows = (0..size.rows)
+            .map(|row| {
+                Row::new(
+                    (0..size.columns)
+                        .map(|column| {
+                            let offset = Position::from(&Coordinates::new(row, column));
+                            if self.is_live(&origin.translate(offset.row, offset.column)) {
+                                Cell::live()
+                            } else {
+                                Cell::dead()
+                            }
+                        })
+                        .collect(),
+                )
+            })
+            .collect();
+
+        let grid = Grid::new(cell_rows).with_topology(Topology::Plane);
+
+        if self.ages.is_none() {
+            return grid;
+        }
+        let ages = CellAges::of(size.rows, size.columns, |coordinates| {
+            let offset = Position::from(coordinates);
+            self.age(&origin.translate(offset.row, offset.column))
+                .unwrap_or(Age::Unborn)
+        });
+        grid.with_ages(ages)
+    }
+
+    /// Number of live neighbours for all the live cells and the cells around them.
+    fn number_of_live_neighbors(&self) -> HashMap<Position, usize> {
+        let mut counters: HashMap<Position, usize> = self
+            .live_cells
+            .iter()
+            .map(|position| (*position, 0))
+            .collect();
+
+        for position in &self.live_cells {
+            for neighbor in position.neighbors() {
+                *counters.entry(neighbor).or_insert(0) += 1;
+            }
+        }
+
+        counters
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::domain::{
+        cell::coordinates::Coordinates,
+        fixtures::glider,
+        grid::{
+            ages::Age, functions::next_generation::next_generation, size::Size, topology::Topology,
+            Grid,
+        },
+        rule::Rule,
+        universe::{position::Position, Universe, DEAD_CELL_MEMORY},
+    };
+
+    #[test]
+    fn a_new_universe_is_empty() {
+        assert!(Universe::new().is_empty());
+    }
+
+    #[test]
+    fn it_should_be_built_from_a_grid() {
+        let universe = Universe::from(&glider());
+
+        assert_eq!(universe.population(), 5);
+        assert!(universe.is_live(&Position::new(0, 1)));
+        assert!(!universe.is_live(&Position::new(0, 0)));
+    }
+
+    #[test]
+    fn it_should_return_the_bounding_box_of_the_live_cells() {
+        let universe = Universe::with_live_cells([Position::new(-2, 3), Position::new(4, -1)]);
+
+        assert_eq!(
+            universe.bounding_box(),
+            Some((Position::new(-2, -1), Position::new(4, 3)))
+        );
+        assert_eq!(Universe::new().bounding_box(), None);
+    }
+
+    #[test]
+    fn the_next_generation_of_an_empty_universe_is_an_empty_universe() {
+        assert!(Universe::new().next_generation(&Rule::default()).is_empty());
+    }
+
+    #[test]
+    fn it_should_calculate_the_next_generation_like_a_grid_on_a_plane() {
+        let grid = Grid::from_str(
+            "⬛⬛⬛⬛⬛⬛
+             ⬛⬛⬜⬛⬛⬛
+             ⬛⬛⬛⬜⬛⬛
+             ⬛⬜⬜⬜⬛⬛
+             ⬛⬛⬛⬛⬛⬛
+             ⬛⬛⬛⬛⬛⬛",
+        )
+        .unwrap()
+        .with_topology(Topology::Plane);
+
+        let universe = Universe::from(&grid).next_generation(&Rule::default());
+
+        assert_eq!(
+            universe.viewport(&Position::new(0, 0), &grid.size()),
+            next_generation(&grid, &Rule::default())
+        );
+    }
+
+    #[test]
+    fn patterns_grow_beyond_any_edge() {
+        // After four generations a glider moves one cell down and one cell right
+        let mut universe = Universe::from(&glider());
+
+        for _ in 0..400 {
+            universe = universe.next_generation(&Rule::default());
+        }
+
+        assert_eq!(
+            universe.bounding_box(),
+            Some((Position::new(100, 100), Position::new(102, 102)))
+        );
+    }
+
+    #[test]
+    fn the_cell_ages_should_be_carried_to_the_next_generation() {
+        let blinker = Universe::with_live_cells([
+            Position::new(0, -1),
+            Position::new(0, 0),
+            Position::new(0, 1),
+        ]);
+
+        let universe = blinker
+            .clone()
+            .with_ages()
+            .next_generation(&Rule::default())
+            .next_generation(&Rule::default());
+
+        assert_eq!(universe.age(&Position::new(0, 0)), Some(Age::Live(3)));
+        assert_eq!(universe.age(&Position::new(0, -1)), Some(Age::Live(1)));
+        assert_eq!(universe.age(&Position::new(-1, 0)), Some(Age::Dead(1)));
+        assert_eq!(universe.age(&Position::new(5, 5)), Some(Age::Unborn));
+        assert_eq!(blinker.age(&Position::new(0, 0)), None);
+        // The ages do not make the universes different
+        assert_eq!(universe, blinker);
+    }
+
+    #[test]
+    fn the_viewport_should_show_the_ages_of_the_universe_cells() {
+        let block = Universe::with_live_cells([
+            Position::new(0, 0),
+            Position::new(0, 1),
+            Position::new(1, 0),
+            Position::new(1, 1),
+        ]);
+        let mut universe = block.with_ages();
+
+        // No viewport is rendered while the block gets older
+        for _ in 0..3 {
+            universe = universe.next_generation(&Rule::default());
+        }
+        let grid = universe.viewport(&Position::new(1, 1), &Size::new(2, 2));
+
+        assert_eq!(grid.cell_with_age(&Coordinates::new(0, 0)).age(), Some(4));
+        assert!(grid.cell_with_age(&Coordinates::new(1, 1)).is_dead());
+    }
+
+    #[test]
+    fn dead_cells_should_be_forgotten_after_a_while() {
+        let mut universe = Universe::with_live_cells([Position::new(0, 0)]).with_ages();
+
+        universe = universe.next_generation(&Rule::default());
+        assert_eq!(universe.age(&Position::new(0, 0)), Some(Age::Dead(1)));
+
+        for _ in 0..DEAD_CELL_MEMORY {
+            universe = universe.next_generation(&Rule::default());
+        }
+        assert_eq!(universe.age(&Position::new(0, 0)), Some(Age::Unborn));
+    }
+
+    #[test]
+    fn it_should_render_a_window_into_the_universe() {
+        let universe = Universe::with_live_cells([Position::new(-1, -1), Position::new(5, 5)]);
+
+        assert_eq!(
+            universe.viewport(&Position::new(-1, -1), &Size::new(2, 2)),
+            Grid::from_str(
+                "⬜⬛
+                 ⬛⬛",
+            )
+            .unwrap()
+            .with_topology(Topology::Plane)
+        );
+    }
+}
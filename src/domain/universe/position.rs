@@ -0,0 +1,83 @@
+use crate::domain::cell::coordinates::Coordinates;
+
+/// Cell position in an unbounded universe.
+///
+/// Unlike grid `Coordinates`, a position can be negative because the
+/// universe grows without limit in all directions.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
+pub struct Position {
+    pub row: i64,
+    pub column: i64,
+}
+
+impl Position {
+    #[must_use]
+    pub fn new(row: i64, column: i64) -> Self {
+        Self { row, column }
+    }
+
+    #[must_use]
+    pub fn translate(&self, rows: i64, columns: i64) -> Self {
+        Self::new(self.row + rows, self.column + columns)
+    }
+
+    /// The eight positions around this one.
+    #[must_use]
+    pub fn neighbors(&self) -> [Position; 8] {
+        [
+            self.translate(-1, -1),
+            self.translate(-1, 0),
+            self.translate(-1, 1),
+            self.translate(0, -1),
+            self.translate(0, 1),
+            self.translate(1, -1),
+            self.translate(1, 0),
+            self.translate(1, 1),
+        ]
+    }
+}
+
+/// Grid coordinates are the same position with the grid left top corner at
+/// position (0,0).
+impl From<&Coordinates> for Position {
+    fn from(coordinates: &Coordinates) -> Self {
+        // A grid with more than `i64::MAX` rows or columns does not fit in memory
+        Self::new(
+            i64::try_from(coordinates.row).expect("the row should fit in an i64"),
+            i64::try_from(coordinates.column).expect("the column should fit in an i64"),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::{cell::coordinates::Coordinates, universe::position::Position};
+
+    #[test]
+    fn a_position_can_be_negative() {
+        let position = Position::new(-1, -2);
+
+        assert_eq!(position.row, -1);
+        assert_eq!(position.column, -2);
+    }
+
+    #[test]
+    fn a_position_can_be_translated_some_rows_or_columns() {
+        assert_eq!(Position::new(0, 0).translate(-1, 1), Position::new(-1, 1));
+    }
+
+    #[test]
+    fn a_position_has_eight_neighbors() {
+        let neighbors = Position::new(0, 0).neighbors();
+
+        assert_eq!(neighbors.len(), 8);
+        assert!(neighbors.contains(&Position::new(-1, -1)));
+        assert!(neighbors.contains(&Position::new(1, 1)));
+        assert!(!neighbors.contains(&Position::new(0, 0)));
+    }
+
+    #[test]
+    fn grid_coordinates_should_be_the_same_position() {
+        assert_eq!(Position::from(&Coordinates::new(1, 2)), Position::new(1, 2));
+    }
+}
@@ -0,0 +1,222 @@
+use std::{
+    cell::RefCell,
+    io::{self, Write},
+    time::Duration,
+};
+
+use gif::{Encoder, Frame, Repeat};
+
+use crate::domain::{
+    grid::Grid,
+    output::{
+        colour::Colour,
+        image::{Bitmap, ImageStyle, Layout},
+        recorder::Recorder,
+    },
+};
+
+/// It writes every recorded generation as a frame of an animated GIF.
+///
+/// The GIF starts with the first generation, when the image size is known,
+/// and it is complete once the recorder is finished.
+pub struct GifRecorder<W: Write> {
+    style: ImageStyle,
+    /// Frame delay, in hundredths of a second.
+    delay: u16,
+    looping: bool,
+    writer: RefCell<Option<W>>,
+    encoder: RefCell<Option<Encoder<W>>>,
+    /// Recording stops at the first error, which is returned by `finish`.
+    error: RefCell<Option<io::Error>>,
+}
+
+impl<W: Write> GifRecorder<W> {
+    /// Every frame is shown for the generation lifetime, rounded down to
+    /// hundredths of a second. The animation loops forever by default.
+    #[must_use]
+    pub fn new(writer: W, style: ImageStyle, generation_lifetime: Duration) -> Self {
+        Self {
+            style,
+            delay: u16::try_from(generation_lifetime.as_millis() / 10).unwrap_or(u16::MAX),
+            looping: true,
+            writer: RefCell::new(Some(writer)),
+            encoder: RefCell::new(None),
+            error: RefCell::new(None),
+        }
+    }
+
+    /// The animation stops at the last generation.
+    #[must_use]
+    pub fn without_looping(mut self) -> Self {
+        self.looping = false;
+        self
+    }
+
+    /// It completes the GIF and returns the writer.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if no generation was recorded, the grid is too big
+    /// for a GIF or a frame could not be written.
+    pub fn finish(self) -> io::Result<W> {
+        if let Some(error) = self.error.into_inner() {
+            return Err(error);
+        }
+
+        match self.encoder.into_inner() {
+            Some(encoder) => encoder.into_inner(),
+            None => Err(io::Error::other("no generations were recorded")),
+        }
+    }
+
+    fn write_frame(&self, generation: &Grid) -> io::Result<()> {
+        // The size is checked before drawing, big images do not fit in memory
+        let layout = Layout::new(generation.rows(), generation.columns(), &self.style);
+        let too_big = |_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "the image is {}x{} pixels, bigger than the GIF limit of {} pixels",
+                    layout.width,
+                    layout.height,
+                    u16::MAX
+                ),
+            )
+        };
+        let width = u16::try_from(layout.width).map_err(too_big)?;
+        let height = u16::try_from(layout.height).map_err(too_big)?;
+        let bitmap = Bitmap::of(generation, &self.style)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+
+        let mut encoder = self.encoder.borrow_mut();
+
+        if encoder.is_none() {
+            let writer = self
+                .writer
+                .borrow_mut()
+                .take()
+                .expect("the writer should only be taken once");
+            let palette: Vec<u8> = self.style.palette().iter().flat_map(Colour::rgb).collect();
+            let mut new_encoder =
+                Encoder::new(writer, width, height, &palette).map_err(io::Error::other)?;
+            new_encoder
+                .set_repeat(if self.looping {
+                    Repeat::Infinite
+                } else {
+                    Repeat::Finite(0)
+                })
+                .map_err(io::Error::other)?;
+            *encoder = Some(new_encoder);
+        }
+
+        let mut frame = Frame::from_indexed_pixels(width, height, bitmap.pixels, None);
+        frame.delay = self.delay;
+
+        encoder
+            .as_mut()
+            .expect("the encoder should have been created")
+            .write_frame(&frame)
+            .map_err(io::Error::other)
+    }
+}
+
+impl<W: Write> Recorder for GifRecorder<W> {
+    fn record(&self, generation: &Grid) {
+        if self.error.borrow().is_some() {
+            return;
+        }
+
+        if let Err(error) = self.write_frame(generation) {
+            *self.error.borrow_mut() = Some(error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io, str::FromStr, time::Duration};
+
+    use gif::{DecodeOptions, Decoder};
+
+    use crate::{
+        domain::{
+            grid::Grid,
+            output::{image::ImageStyle, recorder::Recorder},
+        },
+        infrastructure::animation::GifRecorder,
+    };
+
+    fn style() -> ImageStyle {
+        ImageStyle {
+            cell_size: 2,
+            ..ImageStyle::default()
+        }
+    }
+
+    fn decode(gif: &[u8]) -> Decoder<&[u8]> {
+        let mut options = DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::Indexed);
+        options.read_info(gif).unwrap()
+    }
+
+    #[test]
+    fn every_generation_should_be_a_frame() {
+        let recorder = GifRecorder::new(Vec::new(), style(), Duration::from_millis(250));
+
+        recorder.record(&Grid::from_str("⬜⬛").unwrap());
+        recorder.record(&Grid::from_str("⬛⬜").unwrap());
+
+        let gif = recorder.finish().unwrap();
+        let mut decoder = decode(&gif);
+
+        assert_eq!((decoder.width(), decoder.height()), (4, 2));
+
+        let first = decoder.read_next_frame().unwrap().unwrap();
+        assert_eq!(first.delay, 25);
+        assert_eq!(first.buffer.to_vec(), vec![1, 1, 0, 0, 1, 1, 0, 0]);
+
+        let second = decoder.read_next_frame().unwrap().unwrap();
+        assert_eq!(second.buffer.to_vec(), vec![0, 0, 1, 1, 0, 0, 1, 1]);
+
+        assert!(decoder.read_next_frame().unwrap().is_none());
+    }
+
+    #[test]
+    fn the_animation_should_loop_forever_unless_disabled() {
+        let looping = GifRecorder::new(Vec::new(), style(), Duration::ZERO);
+        looping.record(&Grid::from_str("⬜").unwrap());
+
+        let once = GifRecorder::new(Vec::new(), style(), Duration::ZERO).without_looping();
+        once.record(&Grid::from_str("⬜").unwrap());
+
+        let repeat = |gif: Vec<u8>| {
+            let mut decoder = decode(&gif);
+            decoder.read_next_frame().unwrap();
+            decoder.repeat()
+        };
+
+        assert_eq!(repeat(looping.finish().unwrap()), gif::Repeat::Infinite);
+        assert_eq!(repeat(once.finish().unwrap()), gif::Repeat::Finite(0));
+    }
+
+    #[test]
+    fn images_bigger_than_the_gif_limit_should_not_be_drawn() {
+        let style = ImageStyle {
+            cell_size: 40_000,
+            ..ImageStyle::default()
+        };
+        let recorder = GifRecorder::new(Vec::new(), style, Duration::ZERO);
+
+        recorder.record(&Grid::from_str("⬜⬛").unwrap());
+
+        let error = recorder.finish().unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn a_gif_needs_at_least_one_generation() {
+        let recorder = GifRecorder::new(Vec::new(), style(), Duration::ZERO);
+
+        assert!(recorder.finish().is_err());
+    }
+}
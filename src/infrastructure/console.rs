@@ -1,7 +1,11 @@
-use std::env;
+use std::{
+    env,
+    io::{self, Write},
+};
 
 use crate::domain::output::{printer::Printer, CLEAR_SCREEN};
 
+use super::screen::IncrementalScreen;
 
 /// Capture input arguments from the environment
 #[must_use]
@@ -10,7 +14,10 @@ pub fn arguments() -> Vec<String> {
     args
 }
 
-pub struct Console {}
+pub struct Console {
+    /// Without it, every frame is a full redraw.
+    screen: Option<IncrementalScreen>,
+}
 
 impl Default for Console {
     fn default() -> Self {
@@ -21,7 +28,14 @@ impl Default for Console {
 impl Console {
     #[must_use]
     pub fn new() -> Self {
-        Self {}
+        Self { screen: None }
+    }
+
+    /// Only the cells that changed since the previous frame are redrawn.
+    #[must_use]
+    pub fn with_incremental_rendering(mut self) -> Self {
+        self.screen = Some(IncrementalScreen::default());
+        self
     }
 }
 
@@ -33,4 +47,14 @@ impl Printer for Console {
     fn print(&self, output: &str) {
         print!("{}", &output);
     }
+
+    fn print_frame(&self, frame: &str) {
+        if let Some(screen) = &self.screen {
+            self.print(&screen.render(frame));
+            let _ = io::stdout().flush();
+        } else {
+            self.clear();
+            self.print(frame);
+        }
+    }
 }
@@ -1,2 +1,6 @@
+pub mod animation;
 pub mod console;
+pub mod screen;
+pub mod snapshot;
+pub mod terminal;
 pub mod thread;
@@ -0,0 +1,26 @@
+use std::cell::{Cell, RefCell};
+
+use crossterm::terminal;
+
+use crate::domain::output::renderer::IncrementalRenderer;
+
+/// Incremental rendering for a terminal that can be resized. After a
+/// resize, the next frame is rendered in full.
+#[derive(Default)]
+pub struct IncrementalScreen {
+    renderer: RefCell<IncrementalRenderer>,
+    size: Cell<Option<(u16, u16)>>,
+}
+
+impl IncrementalScreen {
+    /// What has to be written to the terminal to show the frame.
+    pub fn render(&self, frame: &str) -> String {
+        let size = terminal::size().ok();
+
+        if self.size.replace(size) != size {
+            self.renderer.borrow_mut().invalidate();
+        }
+
+        self.renderer.borrow_mut().render(frame)
+    }
+}
@@ -0,0 +1,190 @@
+use std::{
+    cell::{Cell, RefCell},
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+};
+
+use png::{BitDepth, ColorType, Encoder};
+
+use crate::domain::{
+    grid::Grid,
+    output::{
+        colour::Colour,
+        image::{Bitmap, ImageStyle, Layout},
+        recorder::Recorder,
+        snapshot::{numbered_file_name, ImageFormat, Snapshots},
+        svg::svg,
+    },
+};
+
+/// It writes the grid as an indexed-colour PNG image.
+///
+/// # Errors
+///
+/// Will return `Err` if the image is too big or it could not be written.
+pub fn write_png<W: Write>(writer: W, grid: &Grid, style: &ImageStyle) -> io::Result<()> {
+    // The size is checked before drawing, big images do not fit in memory
+    let layout = Layout::new(grid.rows(), grid.columns(), style);
+    let too_big = |_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "the image is {}x{} pixels, bigger than the PNG limit of {} pixels",
+                layout.width,
+                layout.height,
+                u32::MAX
+            ),
+        )
+    };
+    let width = u32::try_from(layout.width).map_err(too_big)?;
+    let height = u32::try_from(layout.height).map_err(too_big)?;
+    let bitmap = Bitmap::of(grid, style)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+
+    let mut encoder = Encoder::new(writer, width, height);
+    encoder.set_color(ColorType::Indexed);
+    encoder.set_depth(BitDepth::Eight);
+    encoder.set_palette(
+        style
+            .palette()
+            .iter()
+            .flat_map(Colour::rgb)
+            .collect::<Vec<u8>>(),
+    );
+
+    let mut writer = encoder.write_header().map_err(io::Error::other)?;
+    writer
+        .write_image_data(&bitmap.pixels)
+        .map_err(io::Error::other)?;
+    writer.finish().map_err(io::Error::other)
+}
+
+/// It saves some of the recorded generations as numbered image files, in
+/// the format for the file extension.
+pub struct SnapshotRecorder {
+    file_name: String,
+    format: ImageFormat,
+    style: ImageStyle,
+    snapshots: Snapshots,
+    /// Used to pad the generation numbers in the file names.
+    last_generation: u32,
+    generation: Cell<u32>,
+    written: RefCell<Vec<String>>,
+    /// Recording stops at the first error, which is returned by `finish`.
+    error: RefCell<Option<io::Error>>,
+}
+
+impl SnapshotRecorder {
+    #[must_use]
+    pub fn new(
+        file_name: &str,
+        format: ImageFormat,
+        style: ImageStyle,
+        snapshots: Snapshots,
+        last_generation: u32,
+    ) -> Self {
+        Self {
+            file_name: file_name.to_owned(),
+            format,
+            style,
+            snapshots,
+            last_generation,
+            generation: Cell::new(0),
+            written: RefCell::new(vec![]),
+            error: RefCell::new(None),
+        }
+    }
+
+    /// It returns the files written.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if an image could not be written.
+    pub fn finish(self) -> io::Result<Vec<String>> {
+        match self.error.into_inner() {
+            Some(error) => Err(error),
+            None => Ok(self.written.into_inner()),
+        }
+    }
+
+    fn write_image(&self, file_name: &str, generation: &Grid) -> io::Result<()> {
+        match self.format {
+            ImageFormat::Png => {
+                let mut writer = BufWriter::new(File::create(file_name)?);
+                write_png(&mut writer, generation, &self.style)?;
+                writer.flush()
+            }
+            ImageFormat::Svg => fs::write(file_name, svg(generation, &self.style)),
+        }
+    }
+}
+
+impl Recorder for SnapshotRecorder {
+    fn record(&self, generation: &Grid) {
+        let number = self.generation.get();
+        self.generation.set(number + 1);
+
+        if self.error.borrow().is_some() || !self.snapshots.includes(number) {
+            return;
+        }
+
+        let file_name = numbered_file_name(&self.file_name, number, self.last_generation);
+
+        match self.write_image(&file_name, generation) {
+            Ok(()) => self.written.borrow_mut().push(file_name),
+            Err(error) => *self.error.borrow_mut() = Some(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io, str::FromStr};
+
+    use png::Decoder;
+
+    use crate::{
+        domain::{
+            grid::Grid,
+            output::{colour::Colour, image::ImageStyle},
+        },
+        infrastructure::snapshot::write_png,
+    };
+
+    #[test]
+    fn it_should_write_an_indexed_colour_png() {
+        let style = ImageStyle {
+            cell_size: 1,
+            live_colour: Colour::new(255, 0, 0),
+            ..ImageStyle::default()
+        };
+        let mut image = Vec::new();
+
+        write_png(&mut image, &Grid::from_str("⬜⬛").unwrap(), &style).unwrap();
+
+        let mut reader = Decoder::new(image.as_slice()).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let frame = reader.next_frame(&mut pixels).unwrap();
+
+        assert_eq!((frame.width, frame.height), (2, 1));
+        assert_eq!(&pixels[..frame.buffer_size()], &[1, 0]);
+        assert_eq!(
+            &reader.info().palette.as_ref().unwrap()[..6],
+            &[0, 0, 0, 255, 0, 0]
+        );
+    }
+
+    #[test]
+    fn images_that_do_not_fit_in_memory_should_not_be_written() {
+        let style = ImageStyle {
+            cell_size: 100,
+            ..ImageStyle::default()
+        };
+        let mut image = Vec::new();
+
+        let error = write_png(&mut image, &Grid::of_dead_cells(2000, 2000), &style).unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert!(image.is_empty());
+    }
+}
@@ -0,0 +1,115 @@
+use std::{
+    io::{self, Write},
+    time::Duration,
+};
+
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    terminal,
+};
+
+use crate::domain::{
+    input::keyboard::{Key, Keyboard},
+    output::{printer::Printer, CLEAR_SCREEN},
+};
+
+use super::screen::IncrementalScreen;
+
+/// Terminal in raw mode, so keys are read as soon as they are pressed.
+///
+/// The terminal goes back to its normal mode when it is dropped.
+pub struct RawTerminal {
+    /// Without it, every frame is a full redraw.
+    screen: Option<IncrementalScreen>,
+}
+
+impl RawTerminal {
+    /// # Errors
+    ///
+    /// Will return `Err` if the terminal can not be switched to raw mode,
+    /// for example when the output is not a terminal.
+    pub fn new() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        Ok(Self { screen: None })
+    }
+
+    /// Only the cells that changed since the previous frame are redrawn.
+    #[must_use]
+    pub fn with_incremental_rendering(mut self) -> Self {
+        self.screen = Some(IncrementalScreen::default());
+        self
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+impl Printer for RawTerminal {
+    fn clear(&self) {
+        self.print(CLEAR_SCREEN);
+    }
+
+    /// In raw mode a new line does not go back to the first column.
+    fn print(&self, output: &str) {
+        let mut stdout = io::stdout();
+        let _ = write!(stdout, "{}", output.replace('\n', "\r\n"));
+        let _ = stdout.flush();
+    }
+
+    fn print_frame(&self, frame: &str) {
+        if let Some(screen) = &self.screen {
+            self.print(&screen.render(frame));
+        } else {
+            self.clear();
+            self.print(frame);
+        }
+    }
+}
+
+impl Keyboard for RawTerminal {
+    fn read_key(&self) -> Option<Key> {
+        while event::poll(Duration::ZERO).ok()? {
+            if let Some(key) = key_for(&event::read().ok()?) {
+                return Some(key);
+            }
+        }
+        None
+    }
+
+    fn wait_for_key(&self) -> Option<Key> {
+        loop {
+            if let Some(key) = key_for(&event::read().ok()?) {
+                return Some(key);
+            }
+        }
+    }
+}
+
+/// Key for a key press event. Other events, like key releases or
+/// terminal resizes, have no key.
+fn key_for(event: &Event) -> Option<Key> {
+    let Event::Key(KeyEvent {
+        code,
+        modifiers,
+        kind: KeyEventKind::Press,
+        ..
+    }) = event
+    else {
+        return None;
+    };
+
+    match code {
+        KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => Some(Key::Interrupt),
+        KeyCode::Char(key) => Some(Key::Char(*key)),
+        KeyCode::Up => Some(Key::Up),
+        KeyCode::Down => Some(Key::Down),
+        KeyCode::Left => Some(Key::Left),
+        KeyCode::Right => Some(Key::Right),
+        KeyCode::Enter => Some(Key::Enter),
+        KeyCode::Esc => Some(Key::Escape),
+        _ => None,
+    }
+}
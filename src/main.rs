@@ -1,5 +1,8 @@
-use game_of_life_kata::{application::app, infrastructure::console};
+use std::process;
+
+use game_of_life_kata::{application::app, infrastructure::console, ui::console::exit_code};
 
 fn main() {
-    let _final_state = app::run(&console::arguments());
+    let outcome = app::run(&console::arguments());
+    process::exit(exit_code(&outcome.stop_reason));
 }
@@ -1,65 +1,660 @@
-use std::{path::Path, process};
+use std::{error::Error, fmt, path::Path, time::Duration};
 
 use text_colorizer::Colorize;
 
-use crate::ui::help::print_usage;
+use crate::domain::{
+    analysis::Periodicity,
+    cell::{
+        alphabet::{Alphabet, ParseAlphabetError},
+        coordinates::Coordinates,
+    },
+    grid::{
+        placement::{Anchor, ParseAnchorError, Placement},
+        topology::{ParseTopologyError, Topology},
+    },
+    outcome::SimulationOutcome,
+    output::{
+        colour::{Colour, ParseColourError},
+        image::{ImageStyle, DEFAULT_CELL_SIZE, MAX_CELL_SIZE},
+        render_mode::{ParseRenderModeError, RenderMode},
+        snapshot::{ImageFormat, Snapshots},
+    },
+    rule::{ParseRuleError, Rule},
+    stability::StopReason,
+    statistics::StatisticsFormat,
+};
 
-const NUMBER_OF_ARGUMENTS: usize = 5;
+const DEFAULT_GENERATIONS: u32 = 1000;
+const DEFAULT_GENERATION_LIFETIME: Duration = Duration::from_secs(1);
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run(Box<Arguments>),
+    Help,
+    Version,
+}
+
+// Every flag is a command line option of its own
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, PartialEq)]
 pub struct Arguments {
     pub pattern_file_path: String, // The path of the file containing the pattern
-    pub rows: u32,                 // Number of rows for the background grid
-    pub columns: u32,              // Number of columns for the background grid
+    pub rows: Option<u32>,         // Number of rows for the background grid
+    pub columns: Option<u32>,      // Number of columns for the background grid
     pub generations: u32,          // Number of generations to run the game
-    pub generation_lifetime: u32,  // Lifetime for a generation in seconds
+    pub generation_lifetime: Duration, // Lifetime for a generation
+    pub rule: Option<Rule>,        // Rule used to calculate the next generation
+    pub topology: Topology,        // How the background grid edges are stitched together
+    pub unbounded: bool,           // Play on an unbounded universe instead of the background grid
+    pub threads: u32,              // Number of threads used to calculate the next generation
+    pub alphabet: Alphabet,        // Chars for the live and dead cells in text patterns and output
+    pub placement: Placement,      // Where the pattern is placed on the background grid
+    pub until_stable: bool,        // Stop on extinction, a still life or an oscillation
+    pub interactive: bool,         // Control the game with the keyboard
+    pub edit: bool,                // Edit the pattern before running the game
+    pub save_path: Option<String>, // File where the editor saves the pattern, the pattern file by default
+    pub full_redraw: bool, // Redraw the whole grid every generation, not only the changed cells
+    pub render_mode: RenderMode, // How the grid cells are drawn
+    pub colour_by_age: bool, // Paint the live cells with a colour for their age
+    pub trail: bool,       // Paint the recently dead cells dimmed
+    pub gif_path: Option<String>, // File where the game is saved as an animated GIF, with no terminal
+    pub cell_size: u32,           // Side of the square drawn for every cell in images, in pixels
+    pub grid_lines: bool,         // Draw a line around every cell in images
+    pub ruler: bool,              // Number the rows and columns in images
+    pub live_colour: Colour,      // Colour of the live cells in images
+    pub dead_colour: Colour,      // Colour of the dead cells in images
+    pub line_colour: Colour,      // Colour of the grid lines and the ruler in images
+    pub image_path: Option<String>, // File where generations are saved as numbered images, with no terminal
+    pub snapshots: Snapshots,       // Generations saved as images
+    pub stats_path: Option<String>, // File where the statistics of every generation are saved
+    pub status_line: bool,          // Show the statistics of the generation under the grid
+    pub analyze: bool, // Tell if the last generation is a still life, an oscillator or a spaceship
+    pub looping: bool, // Loop the animated GIF forever
 }
 
-#[must_use]
-pub fn parse_args(args: &Vec<String>) -> Arguments {
-    check_number_of_mandatory_params(args.len(), NUMBER_OF_ARGUMENTS);
+impl Arguments {
+    fn new(pattern_file_path: String) -> Self {
+        Self {
+            pattern_file_path,
+            rows: None,
+            columns: None,
+            generations: DEFAULT_GENERATIONS,
+            generation_lifetime: DEFAULT_GENERATION_LIFETIME,
+            rule: None,
+            topology: Topology::default(),
+            unbounded: false,
+            threads: 1,
+            alphabet: Alphabet::default(),
+            placement: Placement::default(),
+            until_stable: false,
+            interactive: false,
+            edit: false,
+            save_path: None,
+            full_redraw: false,
+            render_mode: RenderMode::default(),
+            colour_by_age: false,
+            trail: false,
+            gif_path: None,
+            cell_size: u32::try_from(DEFAULT_CELL_SIZE)
+                .expect("the default cell size should fit in a u32"),
+            grid_lines: false,
+            looping: true,
+            ruler: false,
+            live_colour: ImageStyle::default().live_colour,
+            dead_colour: ImageStyle::default().dead_colour,
+            line_colour: ImageStyle::default().line_colour,
+            image_path: None,
+            snapshots: Snapshots::default(),
+            stats_path: None,
+            status_line: false,
+            analyze: false,
+        }
+    }
+}
 
-    Arguments {
-        pattern_file_path: parse_file_path("PATTERN_FILE_PATH", &args[0]),
-        rows: parse_positive_integer("ROWS", &args[1]),
-        columns: parse_positive_integer("COLUMNS", &args[2]),
-        generations: parse_positive_integer("GENERATIONS", &args[3]),
-        generation_lifetime: parse_positive_integer("GENERATION_LIFETIME", &args[4]),
+#[derive(Debug, PartialEq, Eq)]
+pub enum ArgumentsError {
+    MissingPatternFilePath,
+    UnexpectedArgument {
+        argument: String,
+    },
+    UnknownOption {
+        option: String,
+    },
+    FileNotFound {
+        file_path: String,
+    },
+    InvalidPositiveInteger {
+        option: String,
+        value: String,
+    },
+    UnexpectedValue {
+        option: String,
+        value: String,
+    },
+    ConflictingOptions {
+        first: String,
+        second: String,
+    },
+    UnsupportedAgeColours {
+        option: String,
+        render_mode: RenderMode,
+    },
+    InvalidRule {
+        value: String,
+        error: ParseRuleError,
+    },
+    InvalidTopology {
+        value: String,
+        error: ParseTopologyError,
+    },
+    InvalidAlphabet {
+        value: String,
+        error: ParseAlphabetError,
+    },
+    InvalidAnchor {
+        value: String,
+        error: ParseAnchorError,
+    },
+    InvalidOffset {
+        value: String,
+    },
+    InvalidDelay {
+        value: String,
+    },
+    InvalidFps {
+        value: String,
+    },
+    InvalidCellSize {
+        value: String,
+    },
+    MissingSavePath,
+    MissingOutputPath {
+        option: String,
+    },
+    UnknownImageFormat {
+        file_path: String,
+    },
+    UnknownStatisticsFormat {
+        file_path: String,
+    },
+    InvalidColour {
+        option: String,
+        value: String,
+        error: ParseColourError,
+    },
+    InvalidRenderMode {
+        value: String,
+        error: ParseRenderModeError,
+    },
+}
+
+impl Error for ArgumentsError {}
+
+impl fmt::Display for ArgumentsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ArgumentsError::MissingPatternFilePath => {
+                write!(f, "Missing argument PATTERN_FILE_PATH")
+            }
+            ArgumentsError::UnexpectedArgument { argument } => {
+                write!(f, "Unexpected argument {argument}")
+            }
+            ArgumentsError::UnknownOption { option } => write!(f, "Unknown option {option}"),
+            ArgumentsError::FileNotFound { file_path } => write!(f, "File not found {file_path}"),
+            ArgumentsError::InvalidPositiveInteger { option, value } => {
+                write!(
+                    f,
+                    "Option {option} should be a positive integer, got {value}"
+                )
+            }
+            ArgumentsError::UnexpectedValue { option, value } => {
+                write!(f, "Option {option} does not take a value, got {value}")
+            }
+            ArgumentsError::ConflictingOptions { first, second } => {
+                write!(f, "Options {first} and {second} can not be used together")
+            }
+            ArgumentsError::UnsupportedAgeColours {
+                option,
+                render_mode,
+            } => write!(
+                f,
+                "Option {option} can not be used with the {render_mode} render mode"
+            ),
+            ArgumentsError::InvalidRule { error, .. } => write!(f, "{error}"),
+            ArgumentsError::InvalidTopology { error, .. } => write!(f, "{error}"),
+            ArgumentsError::InvalidAlphabet { error, .. } => write!(f, "{error}"),
+            ArgumentsError::InvalidAnchor { error, .. } => write!(f, "{error}"),
+            ArgumentsError::InvalidOffset { value } => write!(f, "Invalid offset {value}"),
+            ArgumentsError::InvalidDelay { value } => write!(f, "Invalid delay {value}"),
+            ArgumentsError::InvalidFps { value } => {
+                write!(f, "Invalid frames per second {value}")
+            }
+            ArgumentsError::InvalidCellSize { value } => write!(f, "Invalid cell size {value}"),
+            ArgumentsError::MissingSavePath => write!(f, "Missing file path for --save-as"),
+            ArgumentsError::MissingOutputPath { option } => {
+                write!(f, "Missing file path for {option}")
+            }
+            ArgumentsError::UnknownImageFormat { file_path } => {
+                write!(f, "Unknown image format for {file_path}")
+            }
+            ArgumentsError::UnknownStatisticsFormat { file_path } => {
+                write!(f, "Unknown statistics format for {file_path}")
+            }
+            ArgumentsError::InvalidColour { error, .. } => write!(f, "{error}"),
+            ArgumentsError::InvalidRenderMode { error, .. } => write!(f, "{error}"),
+        }
     }
 }
 
-// todo:
-// - Add tests for these functions.
+/// It parses the command line arguments (without the program name).
+///
+/// The only positional argument is the pattern file path. Everything else
+/// is an option like `--rows=30` with a default value when omitted.
+///
+/// # Errors
+///
+/// Will return `Err` if any of the arguments is not valid.
+pub fn parse_args(args: &[String]) -> Result<Command, ArgumentsError> {
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        return Ok(Command::Help);
+    }
+    if args.iter().any(|arg| arg == "--version" || arg == "-V") {
+        return Ok(Command::Version);
+    }
+
+    let (options, positionals): (Vec<&String>, Vec<&String>) =
+        args.iter().partition(|arg| arg.starts_with("--"));
 
-fn check_number_of_mandatory_params(number_of_arguments: usize, expected_number: usize) {
-    if number_of_arguments != expected_number {
-        eprintln!(
-            "{} wrong number of arguments: expected {}, got {}",
-            "Invalid arguments:".red().bold(),
-            NUMBER_OF_ARGUMENTS,
-            number_of_arguments
-        );
-        print_usage();
-        process::exit(1);
+    let pattern_file_path = match positionals[..] {
+        [] => return Err(ArgumentsError::MissingPatternFilePath),
+        [pattern_file_path] => parse_file_path(pattern_file_path)?,
+        [_, unexpected, ..] => {
+            return Err(ArgumentsError::UnexpectedArgument {
+                argument: unexpected.clone(),
+            })
+        }
+    };
+
+    let mut arguments = Arguments::new(pattern_file_path);
+
+    for option in options {
+        parse_option(option, &mut arguments)?;
     }
+    check_single_output(&arguments)?;
+    check_age_colours(&arguments)?;
+
+    Ok(Command::Run(Box::new(arguments)))
 }
 
-fn parse_file_path(arg_name: &str, arg_value: &str) -> String {
+fn parse_file_path(arg_value: &str) -> Result<String, ArgumentsError> {
     if !Path::new(arg_value).exists() {
-        print_invalid_file_path_error(arg_name, arg_value);
-        process::exit(1);
+        return Err(ArgumentsError::FileNotFound {
+            file_path: arg_value.to_owned(),
+        });
+    }
+    Ok(arg_value.to_owned())
+}
+
+fn parse_option(option: &str, arguments: &mut Arguments) -> Result<(), ArgumentsError> {
+    let (name, value) = option.split_once('=').unwrap_or((option, ""));
+
+    match name {
+        "--rows" => arguments.rows = Some(parse_non_zero_integer(name, value)?),
+        "--cols" => arguments.columns = Some(parse_non_zero_integer(name, value)?),
+        "--generations" => arguments.generations = parse_positive_integer(name, value)?,
+        "--delay" => arguments.generation_lifetime = parse_delay(value)?,
+        "--fps" => arguments.generation_lifetime = parse_fps(value)?,
+        "--rule" => arguments.rule = Some(parse_rule(value)?),
+        "--topology" => arguments.topology = parse_topology(value)?,
+        "--unbounded" => arguments.unbounded = parse_flag(option)?,
+        "--threads" => arguments.threads = parse_non_zero_integer(name, value)?,
+        "--alphabet" => arguments.alphabet = parse_alphabet(value)?,
+        "--anchor" => arguments.placement = Placement::Anchor(parse_anchor(value)?),
+        "--offset" => arguments.placement = Placement::Offset(parse_offset(value)?),
+        "--until-stable" => arguments.until_stable = parse_flag(option)?,
+        "--interactive" => arguments.interactive = parse_flag(option)?,
+        "--edit" => arguments.edit = parse_flag(option)?,
+        "--save-as" => arguments.save_path = Some(parse_save_path(value)?),
+        "--full-redraw" => arguments.full_redraw = parse_flag(option)?,
+        "--render" => arguments.render_mode = parse_render_mode(value)?,
+        "--age-colours" => arguments.colour_by_age = parse_flag(option)?,
+        "--gif" => arguments.gif_path = Some(parse_output_path(name, value)?),
+        "--cell-size" => arguments.cell_size = parse_cell_size(name, value)?,
+        "--ruler" => arguments.ruler = parse_flag(option)?,
+        "--live-colour" => arguments.live_colour = parse_colour(name, value)?,
+        "--dead-colour" => arguments.dead_colour = parse_colour(name, value)?,
+        "--line-colour" => arguments.line_colour = parse_colour(name, value)?,
+        "--image" => arguments.image_path = Some(parse_image_path(name, value)?),
+        "--at-generation" => {
+            arguments.snapshots = Snapshots::Generation(parse_positive_integer(name, value)?);
+        }
+        "--stats" => arguments.stats_path = Some(parse_stats_path(name, value)?),
+        "--status-line" => arguments.status_line = parse_flag(option)?,
+        "--analyze" => arguments.analyze = parse_flag(option)?,
+        "--every" => arguments.snapshots = Snapshots::Every(parse_non_zero_integer(name, value)?),
+        "--grid-lines" => arguments.grid_lines = parse_flag(option)?,
+        "--no-loop" => arguments.looping = !parse_flag(option)?,
+        "--trail" => {
+            arguments.trail = parse_flag(option)?;
+            arguments.colour_by_age = true;
+        }
+        _ => {
+            return Err(ArgumentsError::UnknownOption {
+                option: name.to_owned(),
+            })
+        }
+    }
+
+    Ok(())
+}
+
+/// A game is either played in the terminal, interactively or not, or
+/// exported without one.
+fn check_single_output(arguments: &Arguments) -> Result<(), ArgumentsError> {
+    let outputs: Vec<&str> = [
+        ("--interactive", arguments.interactive),
+        ("--gif", arguments.gif_path.is_some()),
+        ("--image", arguments.image_path.is_some()),
+    ]
+    .into_iter()
+    .filter_map(|(option, used)| used.then_some(option))
+    .collect();
+
+    match outputs[..] {
+        [first, second, ..] => Err(ArgumentsError::ConflictingOptions {
+            first: first.to_owned(),
+            second: second.to_owned(),
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Cells are only painted by age in the modes drawing one cell per char.
+fn check_age_colours(arguments: &Arguments) -> Result<(), ArgumentsError> {
+    let painted_by_age = arguments.colour_by_age || arguments.trail;
+    if painted_by_age
+        && arguments
+            .render_mode
+            .cell_width(&arguments.alphabet)
+            .is_none()
+    {
+        return Err(ArgumentsError::UnsupportedAgeColours {
+            option: if arguments.trail {
+                "--trail"
+            } else {
+                "--age-colours"
+            }
+            .to_owned(),
+            render_mode: arguments.render_mode,
+        });
+    }
+    Ok(())
+}
+
+/// Flags are enabled by their name alone, they do not take a value.
+fn parse_flag(option: &str) -> Result<bool, ArgumentsError> {
+    match option.split_once('=') {
+        Some((name, value)) => Err(ArgumentsError::UnexpectedValue {
+            option: name.to_owned(),
+            value: value.to_owned(),
+        }),
+        None => Ok(true),
+    }
+}
+
+fn parse_output_path(arg_name: &str, arg_value: &str) -> Result<String, ArgumentsError> {
+    if arg_value.is_empty() {
+        return Err(ArgumentsError::MissingOutputPath {
+            option: arg_name.to_owned(),
+        });
+    }
+    Ok(arg_value.to_owned())
+}
+
+/// The format of the image is the one for the file extension.
+fn parse_image_path(arg_name: &str, arg_value: &str) -> Result<String, ArgumentsError> {
+    let image_path = parse_output_path(arg_name, arg_value)?;
+
+    if ImageFormat::from_file_name(&image_path).is_none() {
+        return Err(ArgumentsError::UnknownImageFormat {
+            file_path: image_path,
+        });
+    }
+    Ok(image_path)
+}
+
+/// The format of the statistics is the one for the file extension.
+fn parse_stats_path(arg_name: &str, arg_value: &str) -> Result<String, ArgumentsError> {
+    let stats_path = parse_output_path(arg_name, arg_value)?;
+
+    if StatisticsFormat::from_file_name(&stats_path).is_none() {
+        return Err(ArgumentsError::UnknownStatisticsFormat {
+            file_path: stats_path,
+        });
+    }
+    Ok(stats_path)
+}
+
+fn parse_colour(arg_name: &str, arg_value: &str) -> Result<Colour, ArgumentsError> {
+    arg_value
+        .parse::<Colour>()
+        .map_err(|error| ArgumentsError::InvalidColour {
+            option: arg_name.to_owned(),
+            value: arg_value.to_owned(),
+            error,
+        })
+}
+
+/// Like `parse_positive_integer`, for sizes and steps that cannot be 0.
+fn parse_non_zero_integer(arg_name: &str, arg_value: &str) -> Result<u32, ArgumentsError> {
+    match parse_positive_integer(arg_name, arg_value)? {
+        0 => Err(ArgumentsError::InvalidPositiveInteger {
+            option: arg_name.to_owned(),
+            value: arg_value.to_owned(),
+        }),
+        integer => Ok(integer),
+    }
+}
+
+/// From 1 pixel to `MAX_CELL_SIZE`, bigger images would not fit in memory.
+fn parse_cell_size(arg_name: &str, arg_value: &str) -> Result<u32, ArgumentsError> {
+    let cell_size = parse_non_zero_integer(arg_name, arg_value)?;
+
+    if cell_size as usize > MAX_CELL_SIZE {
+        return Err(ArgumentsError::InvalidCellSize {
+            value: arg_value.to_owned(),
+        });
+    }
+    Ok(cell_size)
+}
+
+fn parse_save_path(arg_value: &str) -> Result<String, ArgumentsError> {
+    if arg_value.is_empty() {
+        return Err(ArgumentsError::MissingSavePath);
+    }
+    Ok(arg_value.to_owned())
+}
+
+fn parse_rule(arg_value: &str) -> Result<Rule, ArgumentsError> {
+    arg_value
+        .parse::<Rule>()
+        .map_err(|error| ArgumentsError::InvalidRule {
+            value: arg_value.to_owned(),
+            error,
+        })
+}
+
+fn parse_topology(arg_value: &str) -> Result<Topology, ArgumentsError> {
+    arg_value
+        .parse::<Topology>()
+        .map_err(|error| ArgumentsError::InvalidTopology {
+            value: arg_value.to_owned(),
+            error,
+        })
+}
+
+fn parse_alphabet(arg_value: &str) -> Result<Alphabet, ArgumentsError> {
+    arg_value
+        .parse::<Alphabet>()
+        .map_err(|error| ArgumentsError::InvalidAlphabet {
+            value: arg_value.to_owned(),
+            error,
+        })
+}
+
+fn parse_anchor(arg_value: &str) -> Result<Anchor, ArgumentsError> {
+    arg_value
+        .parse::<Anchor>()
+        .map_err(|error| ArgumentsError::InvalidAnchor {
+            value: arg_value.to_owned(),
+            error,
+        })
+}
+
+fn parse_render_mode(arg_value: &str) -> Result<RenderMode, ArgumentsError> {
+    arg_value
+        .parse::<RenderMode>()
+        .map_err(|error| ArgumentsError::InvalidRenderMode {
+            value: arg_value.to_owned(),
+            error,
+        })
+}
+
+fn parse_offset(arg_value: &str) -> Result<Coordinates, ArgumentsError> {
+    arg_value
+        .split_once(',')
+        .and_then(|(row, column)| Some((row.trim().parse().ok()?, column.trim().parse().ok()?)))
+        .map(|(row, column)| Coordinates::new(row, column))
+        .ok_or_else(|| ArgumentsError::InvalidOffset {
+            value: arg_value.to_owned(),
+        })
+}
+
+/// Seconds, with decimals or not (`0.25`, `2s`), or milliseconds (`250ms`).
+fn parse_delay(arg_value: &str) -> Result<Duration, ArgumentsError> {
+    let invalid_delay = || ArgumentsError::InvalidDelay {
+        value: arg_value.to_owned(),
+    };
+
+    if let Some(milliseconds) = arg_value.strip_suffix("ms") {
+        return milliseconds
+            .parse::<u64>()
+            .map(Duration::from_millis)
+            .map_err(|_| invalid_delay());
     }
-    arg_value.to_owned()
+
+    let seconds = arg_value.strip_suffix('s').unwrap_or(arg_value);
+    seconds
+        .parse::<f64>()
+        .ok()
+        .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+        .ok_or_else(invalid_delay)
+}
+
+/// Generation lifetime to show the given number of generations per second.
+fn parse_fps(arg_value: &str) -> Result<Duration, ArgumentsError> {
+    arg_value
+        .parse::<f64>()
+        .ok()
+        .filter(|fps| *fps > 0.0)
+        .and_then(|fps| Duration::try_from_secs_f64(1.0 / fps).ok())
+        .ok_or_else(|| ArgumentsError::InvalidFps {
+            value: arg_value.to_owned(),
+        })
+}
+
+fn parse_positive_integer(arg_name: &str, arg_value: &str) -> Result<u32, ArgumentsError> {
+    arg_value
+        .parse::<u32>()
+        .map_err(|_| ArgumentsError::InvalidPositiveInteger {
+            option: arg_name.to_owned(),
+            value: arg_value.to_owned(),
+        })
 }
-fn parse_positive_integer(arg_name: &str, arg_value: &str) -> u32 {
-    let result = arg_value.parse::<u32>();
-    if result.is_err() {
-        print_invalid_positive_integer_error(arg_name, arg_value);
-        process::exit(1);
+
+pub fn print_arguments_error(error: &ArgumentsError) {
+    match error {
+        ArgumentsError::MissingPatternFilePath => print_missing_pattern_file_path_error(),
+        ArgumentsError::UnexpectedArgument { argument } => {
+            print_unexpected_argument_error(argument);
+        }
+        ArgumentsError::UnknownOption { option } => print_unknown_option_error(option),
+        ArgumentsError::FileNotFound { file_path } => {
+            print_invalid_file_path_error("PATTERN_FILE_PATH", file_path);
+        }
+        ArgumentsError::InvalidPositiveInteger { option, value } => {
+            print_invalid_positive_integer_error(option, value);
+        }
+        ArgumentsError::UnexpectedValue { option, value } => {
+            print_unexpected_value_error(option, value);
+        }
+        ArgumentsError::ConflictingOptions { first, second } => {
+            print_conflicting_options_error(first, second);
+        }
+        ArgumentsError::UnsupportedAgeColours {
+            option,
+            render_mode,
+        } => print_unsupported_age_colours_error(option, *render_mode),
+        ArgumentsError::InvalidRule { value, error } => {
+            print_invalid_rule_error("--rule", value, &error.to_string());
+        }
+        ArgumentsError::InvalidTopology { value, error } => {
+            print_invalid_topology_error("--topology", value, &error.to_string());
+        }
+        ArgumentsError::InvalidAlphabet { value, error } => {
+            print_invalid_alphabet_error("--alphabet", value, &error.to_string());
+        }
+        ArgumentsError::InvalidAnchor { value, error } => {
+            print_invalid_anchor_error("--anchor", value, &error.to_string());
+        }
+        ArgumentsError::InvalidOffset { value } => print_invalid_offset_error("--offset", value),
+        ArgumentsError::InvalidDelay { value } => print_invalid_delay_error("--delay", value),
+        ArgumentsError::InvalidFps { value } => print_invalid_fps_error("--fps", value),
+        ArgumentsError::InvalidCellSize { value } => {
+            print_invalid_cell_size_error("--cell-size", value);
+        }
+        ArgumentsError::MissingSavePath => print_missing_save_path_error("--save-as"),
+        ArgumentsError::MissingOutputPath { option } => print_missing_output_path_error(option),
+        ArgumentsError::UnknownImageFormat { file_path } => {
+            print_unknown_image_format_error("--image", file_path);
+        }
+        ArgumentsError::UnknownStatisticsFormat { file_path } => {
+            print_unknown_statistics_format_error("--stats", file_path);
+        }
+        ArgumentsError::InvalidColour {
+            option,
+            value,
+            error,
+        } => print_invalid_colour_error(option, value, &error.to_string()),
+        ArgumentsError::InvalidRenderMode { value, error } => {
+            print_invalid_render_mode_error("--render", value, &error.to_string());
+        }
     }
-    result.unwrap()
 }
 
+pub fn print_invalid_pattern_error(file_path: &str, error: &str) {
+    eprintln!(
+        "{}: file {} should contain a valid pattern. {}.",
+        "Invalid pattern".red(),
+        file_path.green(),
+        error,
+    );
+}
+fn print_missing_pattern_file_path_error() {
+    eprintln!(
+        "{}: missing argument {}.",
+        "Invalid arguments".red(),
+        "PATTERN_FILE_PATH".green(),
+    );
+}
+fn print_unexpected_argument_error(argument: &str) {
+    eprintln!(
+        "{}: unexpected argument {}. Options are written like --rows=30.",
+        "Invalid arguments".red(),
+        argument.green(),
+    );
+}
 fn print_invalid_file_path_error(arg_name: &str, arg_value: &str) {
     eprintln!(
         "{}: argument {} should be a valid file path, got {}.",
@@ -76,3 +671,652 @@ fn print_invalid_positive_integer_error(arg_name: &str, arg_value: &str) {
         arg_value.green(),
     );
 }
+fn print_unexpected_value_error(arg_name: &str, arg_value: &str) {
+    eprintln!(
+        "{}: argument {} does not take a value, got {}.",
+        "Invalid argument".red(),
+        arg_name.green(),
+        arg_value.green(),
+    );
+}
+fn print_conflicting_options_error(first: &str, second: &str) {
+    eprintln!(
+        "{}: arguments {} and {} can not be used together.",
+        "Invalid argument".red(),
+        first.green(),
+        second.green(),
+    );
+}
+fn print_unsupported_age_colours_error(arg_name: &str, render_mode: RenderMode) {
+    eprintln!(
+        "{}: argument {} paints every cell by age, the {} render mode draws several cells per char.",
+        "Invalid argument".red(),
+        arg_name.green(),
+        render_mode.to_string().green(),
+    );
+}
+fn print_invalid_rule_error(arg_name: &str, arg_value: &str, error: &str) {
+    eprintln!(
+        "{}: argument {} should be a valid rule-string like B3/S23, got {}. {}.",
+        "Invalid argument".red(),
+        arg_name.green(),
+        arg_value.green(),
+        error,
+    );
+}
+fn print_invalid_topology_error(arg_name: &str, arg_value: &str, error: &str) {
+    eprintln!(
+        "{}: argument {} should be one of plane, torus, horizontal-cylinder, vertical-cylinder, klein-bottle or cross-surface, got {}. {}.",
+        "Invalid argument".red(),
+        arg_name.green(),
+        arg_value.green(),
+        error,
+    );
+}
+fn print_invalid_alphabet_error(arg_name: &str, arg_value: &str, error: &str) {
+    eprintln!(
+        "{}: argument {} should be the live cell char followed by the dead cell char, like #., got {}. {}.",
+        "Invalid argument".red(),
+        arg_name.green(),
+        arg_value.green(),
+        error,
+    );
+}
+fn print_invalid_anchor_error(arg_name: &str, arg_value: &str, error: &str) {
+    eprintln!(
+        "{}: argument {} should be one of top-left, top, top-right, left, center, right, bottom-left, bottom or bottom-right, got {}. {}.",
+        "Invalid argument".red(),
+        arg_name.green(),
+        arg_value.green(),
+        error,
+    );
+}
+fn print_invalid_render_mode_error(arg_name: &str, arg_value: &str, error: &str) {
+    eprintln!(
+        "{}: argument {} should be one of text, ascii, half-block or braille, got {}. {}.",
+        "Invalid argument".red(),
+        arg_name.green(),
+        arg_value.green(),
+        error,
+    );
+}
+fn print_invalid_offset_error(arg_name: &str, arg_value: &str) {
+    eprintln!(
+        "{}: argument {} should be a row and a column separated by a comma, like 10,20, got {}.",
+        "Invalid argument".red(),
+        arg_name.green(),
+        arg_value.green(),
+    );
+}
+fn print_invalid_delay_error(arg_name: &str, arg_value: &str) {
+    eprintln!(
+        "{}: argument {} should be a number of seconds like 0.5 or milliseconds like 250ms, got {}.",
+        "Invalid argument".red(),
+        arg_name.green(),
+        arg_value.green(),
+    );
+}
+fn print_invalid_fps_error(arg_name: &str, arg_value: &str) {
+    eprintln!(
+        "{}: argument {} should be a number of generations per second greater than 0, got {}.",
+        "Invalid argument".red(),
+        arg_name.green(),
+        arg_value.green(),
+    );
+}
+fn print_invalid_cell_size_error(arg_name: &str, arg_value: &str) {
+    eprintln!(
+        "{}: argument {} should be a number of pixels from 1 to {}, got {}.",
+        "Invalid argument".red(),
+        arg_name.green(),
+        MAX_CELL_SIZE,
+        arg_value.green(),
+    );
+}
+fn print_missing_save_path_error(arg_name: &str) {
+    eprintln!(
+        "{}: argument {} should be the path of the file where the pattern is saved.",
+        "Invalid argument".red(),
+        arg_name.green(),
+    );
+}
+fn print_missing_output_path_error(arg_name: &str) {
+    eprintln!(
+        "{}: argument {} should be the path of the file where the game is saved.",
+        "Invalid argument".red(),
+        arg_name.green(),
+    );
+}
+fn print_unknown_image_format_error(arg_name: &str, arg_value: &str) {
+    eprintln!(
+        "{}: argument {} should be a file with the png or svg extension, got {}.",
+        "Invalid argument".red(),
+        arg_name.green(),
+        arg_value.green(),
+    );
+}
+fn print_unknown_statistics_format_error(arg_name: &str, arg_value: &str) {
+    eprintln!(
+        "{}: argument {} should be a file with the csv or json extension, got {}.",
+        "Invalid argument".red(),
+        arg_name.green(),
+        arg_value.green(),
+    );
+}
+fn print_invalid_colour_error(arg_name: &str, arg_value: &str, error: &str) {
+    eprintln!(
+        "{}: argument {} should be a colour like #ff8000, got {}. {}.",
+        "Invalid argument".red(),
+        arg_name.green(),
+        arg_value.green(),
+        error,
+    );
+}
+pub fn print_pattern_does_not_fit_error(error: &str) {
+    eprintln!(
+        "{}: {}. Use a bigger grid or place the pattern with {} or {}.",
+        "Invalid placement".red(),
+        error,
+        "--anchor".green(),
+        "--offset".green(),
+    );
+}
+pub fn print_unbounded_b0_rule_error(error: &str) {
+    eprintln!(
+        "{}: {}. Play it on the background grid, without {}.",
+        "Invalid rule".red(),
+        error,
+        "--unbounded".green(),
+    );
+}
+fn print_unknown_option_error(option_name: &str) {
+    eprintln!("{}: {}.", "Unknown option".red(), option_name.green());
+}
+
+pub fn print_terminal_error(error: &str) {
+    eprintln!(
+        "{}: {}. The {} option needs a terminal.",
+        "Terminal error".red(),
+        error,
+        "--interactive".green(),
+    );
+}
+
+pub fn print_export_error(file_path: &str, error: &str) {
+    eprintln!(
+        "{}: the game could not be saved to {}. {}.",
+        "Export error".red(),
+        file_path.green(),
+        error,
+    );
+}
+
+pub fn print_export_done(file_path: &str, generations: u32) {
+    eprintln!(
+        "{}: {} generations saved to {}.",
+        "Exported".green(),
+        generations,
+        file_path.green(),
+    );
+}
+
+pub fn print_images_done(file_paths: &[String]) {
+    let (Some(first), Some(last)) = (file_paths.first(), file_paths.last()) else {
+        return;
+    };
+
+    if first == last {
+        eprintln!("{}: image saved to {}.", "Exported".green(), first.green());
+    } else {
+        eprintln!(
+            "{}: {} images saved, from {} to {}.",
+            "Exported".green(),
+            file_paths.len(),
+            first.green(),
+            last.green(),
+        );
+    }
+}
+
+pub fn print_analysis(periodicity: Option<&Periodicity>, max_period: usize) {
+    match periodicity {
+        Some(periodicity) => eprintln!("{}: {}.", "Analysis".green(), periodicity),
+        None => eprintln!(
+            "{}: the last generation does not repeat itself within {} generations.",
+            "Analysis".green(),
+            max_period,
+        ),
+    }
+}
+
+pub fn print_simulation_stats(outcome: &SimulationOutcome) {
+    eprintln!(
+        "{}: {} generations, {} live cells, {:.1}s.",
+        "Game over".green(),
+        outcome.generations,
+        outcome.final_population(),
+        outcome.elapsed.as_secs_f64(),
+    );
+}
+
+pub fn print_stop_reason(stop_reason: &StopReason, generation: u32) {
+    eprintln!(
+        "{} at generation {}: {}.",
+        "Stopped".green(),
+        generation.to_string().green(),
+        stop_reason,
+    );
+}
+
+/// Process exit code for each way the game can end. Invalid arguments or
+/// patterns exit with 1. Quitting an interactive game is a clean exit.
+#[must_use]
+pub fn exit_code(stop_reason: &StopReason) -> i32 {
+    match stop_reason {
+        StopReason::GenerationLimit | StopReason::Quit => 0,
+        StopReason::Extinction => 10,
+        StopReason::StillLife => 11,
+        StopReason::Oscillation { .. } => 12,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{
+        domain::{
+            cell::{alphabet::Alphabet, coordinates::Coordinates},
+            grid::{
+                placement::{Anchor, Placement},
+                topology::Topology,
+            },
+            output::{colour::Colour, render_mode::RenderMode, snapshot::Snapshots},
+            rule::{ParseRuleError, Rule},
+        },
+        ui::console::{parse_args, Arguments, ArgumentsError, Command},
+    };
+
+    const PATTERN: &str = "./patterns/glider.txt";
+
+    fn args(command_line: &str) -> Vec<String> {
+        command_line
+            .split_whitespace()
+            .map(ToOwned::to_owned)
+            .collect()
+    }
+
+    fn parse_run(command_line: &str) -> Arguments {
+        match parse_args(&args(command_line)) {
+            Ok(Command::Run(arguments)) => *arguments,
+            other => panic!("expected arguments to run the game, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn only_the_pattern_file_path_is_required() {
+        let arguments = parse_run(PATTERN);
+
+        assert_eq!(arguments.pattern_file_path, PATTERN);
+        assert_eq!(arguments.rows, None);
+        assert_eq!(arguments.columns, None);
+        assert_eq!(arguments.generations, 1000);
+        assert_eq!(arguments.generation_lifetime, Duration::from_secs(1));
+        assert_eq!(arguments.rule, None);
+        assert_eq!(arguments.topology, Topology::Torus);
+        assert_eq!(arguments.placement, Placement::default());
+        assert!(!arguments.until_stable);
+        assert!(!arguments.interactive);
+        assert!(!arguments.edit);
+        assert_eq!(arguments.save_path, None);
+        assert!(!arguments.full_redraw);
+        assert_eq!(arguments.render_mode, RenderMode::Text);
+        assert!(!arguments.colour_by_age);
+        assert!(!arguments.trail);
+        assert_eq!(arguments.gif_path, None);
+        assert_eq!(arguments.cell_size, 4);
+        assert!(!arguments.grid_lines);
+        assert!(arguments.looping);
+        assert!(!arguments.ruler);
+        assert_eq!(arguments.image_path, None);
+        assert_eq!(arguments.snapshots, Snapshots::Generation(0));
+        assert_eq!(arguments.stats_path, None);
+        assert!(!arguments.status_line);
+        assert!(!arguments.analyze);
+    }
+
+    #[test]
+    fn it_should_parse_the_named_options() {
+        let arguments = parse_run(&format!(
+            "{PATTERN} --rows=30 --cols=60 --generations=10 --delay=0 --rule=B36/S23 \
+             --topology=plane --unbounded --threads=4 --alphabet=#. --anchor=top-left \
+             --until-stable --interactive --edit --save-as=glider.rle \
+             --full-redraw --render=ascii --trail --cell-size=8 \
+             --grid-lines --no-loop --ruler --live-colour=#ff8000 --dead-colour=ffffff \
+             --line-colour=#000000 --every=10 --stats=glider.csv \
+             --status-line --analyze"
+        ));
+
+        assert_eq!(arguments.rows, Some(30));
+        assert_eq!(arguments.columns, Some(60));
+        assert_eq!(arguments.generations, 10);
+        assert_eq!(arguments.generation_lifetime, Duration::ZERO);
+        assert_eq!(arguments.rule, Some(Rule::new(&[3, 6], &[2, 3])));
+        assert_eq!(arguments.topology, Topology::Plane);
+        assert!(arguments.unbounded);
+        assert_eq!(arguments.threads, 4);
+        assert_eq!(arguments.alphabet, Alphabet::new('#', '.'));
+        assert_eq!(arguments.placement, Placement::Anchor(Anchor::TopLeft));
+        assert!(arguments.until_stable);
+        assert!(arguments.interactive);
+        assert!(arguments.edit);
+        assert_eq!(arguments.save_path, Some("glider.rle".to_owned()));
+        assert!(arguments.full_redraw);
+        assert_eq!(arguments.render_mode, RenderMode::Ascii);
+        assert!(arguments.colour_by_age);
+        assert!(arguments.trail);
+        assert_eq!(arguments.cell_size, 8);
+        assert!(arguments.grid_lines);
+        assert!(!arguments.looping);
+        assert!(arguments.ruler);
+        assert_eq!(arguments.live_colour, Colour::new(255, 128, 0));
+        assert_eq!(arguments.dead_colour, Colour::new(255, 255, 255));
+        assert_eq!(arguments.line_colour, Colour::new(0, 0, 0));
+        assert_eq!(arguments.snapshots, Snapshots::Every(10));
+        assert_eq!(arguments.stats_path, Some("glider.csv".to_owned()));
+        assert!(arguments.status_line);
+        assert!(arguments.analyze);
+    }
+
+    #[test]
+    fn the_game_can_be_exported_to_a_gif() {
+        assert_eq!(
+            parse_run(&format!("{PATTERN} --gif=glider.gif")).gif_path,
+            Some("glider.gif".to_owned())
+        );
+    }
+
+    #[test]
+    fn the_game_can_be_exported_to_images() {
+        assert_eq!(
+            parse_run(&format!("{PATTERN} --image=glider.svg")).image_path,
+            Some("glider.svg".to_owned())
+        );
+    }
+
+    #[test]
+    fn an_exported_game_can_not_be_interactive() {
+        assert_eq!(
+            parse_args(&args(&format!("{PATTERN} --interactive --gif=glider.gif"))),
+            Err(ArgumentsError::ConflictingOptions {
+                first: "--interactive".to_owned(),
+                second: "--gif".to_owned()
+            })
+        );
+        assert_eq!(
+            parse_args(&args(&format!(
+                "{PATTERN} --image=glider.svg --interactive"
+            ))),
+            Err(ArgumentsError::ConflictingOptions {
+                first: "--interactive".to_owned(),
+                second: "--image".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn the_game_can_only_be_exported_one_way() {
+        assert_eq!(
+            parse_args(&args(&format!(
+                "{PATTERN} --gif=glider.gif --image=glider.svg"
+            ))),
+            Err(ArgumentsError::ConflictingOptions {
+                first: "--gif".to_owned(),
+                second: "--image".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn cells_should_only_be_painted_by_age_one_per_char() {
+        assert_eq!(
+            parse_args(&args(&format!("{PATTERN} --render=braille --trail"))),
+            Err(ArgumentsError::UnsupportedAgeColours {
+                option: "--trail".to_owned(),
+                render_mode: RenderMode::Braille
+            })
+        );
+        assert_eq!(
+            parse_args(&args(&format!(
+                "{PATTERN} --age-colours --render=half-block"
+            ))),
+            Err(ArgumentsError::UnsupportedAgeColours {
+                option: "--age-colours".to_owned(),
+                render_mode: RenderMode::HalfBlock
+            })
+        );
+    }
+
+    #[test]
+    fn options_can_go_before_the_pattern_file_path() {
+        assert_eq!(parse_run(&format!("--rows=5 {PATTERN}")).rows, Some(5));
+    }
+
+    #[test]
+    fn it_should_parse_the_pattern_offset() {
+        assert_eq!(
+            parse_run(&format!("{PATTERN} --offset=5,10")).placement,
+            Placement::Offset(Coordinates::new(5, 10))
+        );
+    }
+
+    #[test]
+    fn the_delay_can_be_given_in_seconds_with_decimals_or_in_milliseconds() {
+        let delay =
+            |value: &str| parse_run(&format!("{PATTERN} --delay={value}")).generation_lifetime;
+
+        assert_eq!(delay("2"), Duration::from_secs(2));
+        assert_eq!(delay("0.25"), Duration::from_millis(250));
+        assert_eq!(delay("1.5s"), Duration::from_millis(1500));
+        assert_eq!(delay("40ms"), Duration::from_millis(40));
+    }
+
+    #[test]
+    fn the_generation_lifetime_can_be_given_in_frames_per_second() {
+        assert_eq!(
+            parse_run(&format!("{PATTERN} --fps=4")).generation_lifetime,
+            Duration::from_millis(250)
+        );
+    }
+
+    #[test]
+    fn it_should_fail_with_an_invalid_delay() {
+        assert_eq!(
+            parse_args(&args(&format!("{PATTERN} --delay=-1"))),
+            Err(ArgumentsError::InvalidDelay {
+                value: "-1".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_fail_with_zero_frames_per_second() {
+        assert_eq!(
+            parse_args(&args(&format!("{PATTERN} --fps=0"))),
+            Err(ArgumentsError::InvalidFps {
+                value: "0".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_fail_without_a_file_path_to_save_the_pattern() {
+        assert_eq!(
+            parse_args(&args(&format!("{PATTERN} --edit --save-as="))),
+            Err(ArgumentsError::MissingSavePath)
+        );
+    }
+
+    #[test]
+    fn only_png_and_svg_images_are_supported() {
+        assert_eq!(
+            parse_args(&args(&format!("{PATTERN} --image=glider.jpg"))),
+            Err(ArgumentsError::UnknownImageFormat {
+                file_path: "glider.jpg".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn only_csv_and_json_statistics_are_supported() {
+        assert_eq!(
+            parse_args(&args(&format!("{PATTERN} --stats=glider.txt"))),
+            Err(ArgumentsError::UnknownStatisticsFormat {
+                file_path: "glider.txt".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn cells_should_be_at_least_one_pixel_big() {
+        assert_eq!(
+            parse_args(&args(&format!("{PATTERN} --gif=glider.gif --cell-size=0"))),
+            Err(ArgumentsError::InvalidPositiveInteger {
+                option: "--cell-size".to_owned(),
+                value: "0".to_owned(),
+            })
+        );
+    }
+
+    #[test]
+    fn cells_should_not_be_too_big_to_draw() {
+        assert_eq!(
+            parse_args(&args(&format!(
+                "{PATTERN} --gif=glider.gif --cell-size=100000"
+            ))),
+            Err(ArgumentsError::InvalidCellSize {
+                value: "100000".to_owned(),
+            })
+        );
+        assert_eq!(
+            parse_run(&format!("{PATTERN} --gif=glider.gif --cell-size=100")).cell_size,
+            100
+        );
+    }
+
+    #[test]
+    fn it_should_ask_for_help() {
+        assert_eq!(parse_args(&args("--help")), Ok(Command::Help));
+        assert_eq!(
+            parse_args(&args(&format!("{PATTERN} -h"))),
+            Ok(Command::Help)
+        );
+    }
+
+    #[test]
+    fn it_should_ask_for_the_version() {
+        assert_eq!(parse_args(&args("--version")), Ok(Command::Version));
+        assert_eq!(parse_args(&args("-V")), Ok(Command::Version));
+    }
+
+    #[test]
+    fn it_should_fail_without_the_pattern_file_path() {
+        assert_eq!(
+            parse_args(&args("--rows=30")),
+            Err(ArgumentsError::MissingPatternFilePath)
+        );
+    }
+
+    #[test]
+    fn it_should_fail_with_an_unexpected_positional_argument() {
+        assert_eq!(
+            parse_args(&args(&format!("{PATTERN} 30 60"))),
+            Err(ArgumentsError::UnexpectedArgument {
+                argument: "30".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_fail_when_the_pattern_file_does_not_exist() {
+        assert_eq!(
+            parse_args(&args("./patterns/missing.txt")),
+            Err(ArgumentsError::FileNotFound {
+                file_path: "./patterns/missing.txt".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_fail_with_an_unknown_option() {
+        assert_eq!(
+            parse_args(&args(&format!("{PATTERN} --speed=3"))),
+            Err(ArgumentsError::UnknownOption {
+                option: "--speed".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_fail_with_an_invalid_positive_integer() {
+        assert_eq!(
+            parse_args(&args(&format!("{PATTERN} --rows=-3"))),
+            Err(ArgumentsError::InvalidPositiveInteger {
+                option: "--rows".to_owned(),
+                value: "-3".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn grids_and_thread_bands_should_not_be_empty() {
+        for option in ["--rows", "--cols", "--threads"] {
+            assert_eq!(
+                parse_args(&args(&format!("{PATTERN} {option}=0"))),
+                Err(ArgumentsError::InvalidPositiveInteger {
+                    option: option.to_owned(),
+                    value: "0".to_owned()
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn flags_should_not_take_a_value() {
+        assert_eq!(
+            parse_args(&args(&format!("{PATTERN} --unbounded=false"))),
+            Err(ArgumentsError::UnexpectedValue {
+                option: "--unbounded".to_owned(),
+                value: "false".to_owned()
+            })
+        );
+        assert_eq!(
+            parse_args(&args(&format!("{PATTERN} --no-loop="))),
+            Err(ArgumentsError::UnexpectedValue {
+                option: "--no-loop".to_owned(),
+                value: String::new()
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_fail_with_an_invalid_rule() {
+        assert_eq!(
+            parse_args(&args(&format!("{PATTERN} --rule=B3S23"))),
+            Err(ArgumentsError::InvalidRule {
+                value: "B3S23".to_owned(),
+                error: ParseRuleError::MissingSeparator
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_fail_with_an_invalid_offset() {
+        assert_eq!(
+            parse_args(&args(&format!("{PATTERN} --offset=5"))),
+            Err(ArgumentsError::InvalidOffset {
+                value: "5".to_owned()
+            })
+        );
+    }
+}
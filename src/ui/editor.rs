@@ -0,0 +1,383 @@
+use std::{fmt::Write, io};
+
+use crate::domain::{
+    cell::{alphabet::Alphabet, coordinates::Coordinates, Cell},
+    grid::{
+        functions::transform::{flip_horizontally, flip_vertically, rotate_clockwise},
+        Grid,
+    },
+    input::keyboard::{Key, Keyboard},
+    output::printer::Printer,
+};
+
+/// Escape codes to highlight the cell under the cursor.
+const REVERSE_VIDEO: &str = "\x1B[7m";
+const NORMAL_VIDEO: &str = "\x1B[27m";
+
+const HELP: &str = "arrows/hjkl: move  space: toggle  c: clear  f: fill  o: rotate  \
+                    m: flip horizontally  v: flip vertically  s: save  enter: run  q: quit";
+
+/// What the player can do in the editor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditorCommand {
+    Up,
+    Down,
+    Left,
+    Right,
+    /// Toggle the cell under the cursor.
+    Toggle,
+    /// Kill all the cells.
+    Clear,
+    /// Make all the cells alive.
+    Fill,
+    /// Rotate the grid 90 degrees clockwise.
+    Rotate,
+    FlipHorizontally,
+    FlipVertically,
+    Save,
+    Run,
+    Quit,
+}
+
+impl EditorCommand {
+    /// Command for the given key, if the key has one.
+    #[must_use]
+    pub fn for_key(key: Key) -> Option<Self> {
+        match key {
+            Key::Up | Key::Char('k') => Some(EditorCommand::Up),
+            Key::Down | Key::Char('j') => Some(EditorCommand::Down),
+            Key::Left | Key::Char('h') => Some(EditorCommand::Left),
+            Key::Right | Key::Char('l') => Some(EditorCommand::Right),
+            Key::Char(' ' | 'x') => Some(EditorCommand::Toggle),
+            Key::Char('c') => Some(EditorCommand::Clear),
+            Key::Char('f') => Some(EditorCommand::Fill),
+            Key::Char('o') => Some(EditorCommand::Rotate),
+            Key::Char('m') => Some(EditorCommand::FlipHorizontally),
+            Key::Char('v') => Some(EditorCommand::FlipVertically),
+            Key::Char('s') => Some(EditorCommand::Save),
+            Key::Enter => Some(EditorCommand::Run),
+            Key::Char('q') | Key::Escape | Key::Interrupt => Some(EditorCommand::Quit),
+            Key::Char(_) => None,
+        }
+    }
+}
+
+/// How the player left the editor.
+#[derive(Debug, PartialEq)]
+pub enum EditorExit {
+    /// Run the game with the edited grid.
+    Run(Grid),
+    Quit,
+}
+
+/// A grid with a cursor to edit it cell by cell.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Editor {
+    grid: Grid,
+    cursor: Coordinates,
+}
+
+impl Editor {
+    /// The cursor starts at the left top corner.
+    #[must_use]
+    pub fn new(grid: Grid) -> Self {
+        Self {
+            grid,
+            cursor: Coordinates::new(0, 0),
+        }
+    }
+
+    #[must_use]
+    pub fn grid(&self) -> &Grid {
+        &self.grid
+    }
+
+    #[must_use]
+    pub fn cursor(&self) -> Coordinates {
+        self.cursor
+    }
+
+    /// It applies a command that changes the grid or moves the cursor.
+    /// The cursor never leaves the grid. Other commands are ignored.
+    pub fn apply(&mut self, command: EditorCommand) {
+        let last_row = self.grid.rows().saturating_sub(1);
+        let last_column = self.grid.columns().saturating_sub(1);
+
+        match command {
+            EditorCommand::Up => self.cursor.row = self.cursor.row.saturating_sub(1),
+            EditorCommand::Down => self.cursor.row = (self.cursor.row + 1).min(last_row),
+            EditorCommand::Left => self.cursor.column = self.cursor.column.saturating_sub(1),
+            EditorCommand::Right => {
+                self.cursor.column = (self.cursor.column + 1).min(last_column);
+            }
+            EditorCommand::Toggle => self.toggle(),
+            EditorCommand::Clear => self.fill_with(&Cell::dead()),
+            EditorCommand::Fill => self.fill_with(&Cell::live()),
+            EditorCommand::Rotate => self.transform(rotate_clockwise),
+            EditorCommand::FlipHorizontally => self.transform(flip_horizontally),
+            EditorCommand::FlipVertically => self.transform(flip_vertically),
+            EditorCommand::Save | EditorCommand::Run | EditorCommand::Quit => {}
+        }
+    }
+
+    /// It renders the grid with the cell under the cursor highlighted.
+    #[must_use]
+    pub fn render(&self, alphabet: &Alphabet) -> String {
+        let mut output = String::new();
+
+        for row in 0..self.grid.rows() {
+            for column in 0..self.grid.columns() {
+                let coordinates = Coordinates::new(row, column);
+                let state = self.grid.get_cell(&coordinates).state();
+                let char = alphabet.char_for(&state);
+
+                if coordinates == self.cursor {
+                    write!(output, "{REVERSE_VIDEO}{char}{NORMAL_VIDEO}").unwrap();
+                } else {
+                    output.push(char);
+                }
+            }
+            output.push('\n');
+        }
+
+        output
+    }
+
+    fn toggle(&mut self) {
+        if self.grid.position_is_valid(&self.cursor) {
+            let cell = if self.grid.is_live(&self.cursor) {
+                Cell::dead()
+            } else {
+                Cell::live()
+            };
+            self.grid.set_cell(&self.cursor, &cell);
+        }
+    }
+
+    fn fill_with(&mut self, cell: &Cell) {
+        for coordinates in self.grid.iter() {
+            self.grid.set_cell(&coordinates, cell);
+        }
+    }
+
+    fn transform(&mut self, transformation: fn(&Grid) -> Grid) {
+        self.grid = transformation(&self.grid);
+        self.cursor = Coordinates::new(
+            self.cursor.row.min(self.grid.rows().saturating_sub(1)),
+            self.cursor
+                .column
+                .min(self.grid.columns().saturating_sub(1)),
+        );
+    }
+}
+
+/// It lets the player edit the grid until they run the game or quit.
+///
+/// The grid is saved with the `save` function, which writes it to the
+/// file at `save_path`.
+pub fn edit<P: Printer, K: Keyboard>(
+    grid: Grid,
+    alphabet: &Alphabet,
+    save_path: &str,
+    save: impl Fn(&Grid) -> io::Result<()>,
+    console: &P,
+    keyboard: &K,
+) -> EditorExit {
+    let mut editor = Editor::new(grid);
+    let mut status = format!("Editing {save_path}");
+
+    loop {
+        console.clear();
+        console.print(&editor.render(alphabet));
+        console.print(&format!("{HELP}\n{status}\n"));
+
+        let Some(key) = keyboard.wait_for_key() else {
+            return EditorExit::Quit;
+        };
+
+        match EditorCommand::for_key(key) {
+            Some(EditorCommand::Save) => {
+                status = match save(editor.grid()) {
+                    Ok(()) => format!("Saved to {save_path}"),
+                    Err(error) => format!("Could not save to {save_path}: {error}"),
+                };
+            }
+            Some(EditorCommand::Run) => return EditorExit::Run(editor.grid().clone()),
+            Some(EditorCommand::Quit) => return EditorExit::Quit,
+            Some(command) => editor.apply(command),
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, collections::VecDeque, io, str::FromStr};
+
+    use crate::{
+        domain::{
+            cell::{alphabet::Alphabet, coordinates::Coordinates},
+            grid::Grid,
+            input::keyboard::{Key, Keyboard},
+            output::logger::Logger,
+        },
+        ui::editor::{edit, Editor, EditorCommand, EditorExit},
+    };
+
+    struct FakeKeyboard {
+        keys: RefCell<VecDeque<Key>>,
+    }
+
+    impl Keyboard for FakeKeyboard {
+        fn read_key(&self) -> Option<Key> {
+            self.keys.borrow_mut().pop_front()
+        }
+
+        fn wait_for_key(&self) -> Option<Key> {
+            self.read_key()
+        }
+    }
+
+    fn pressing(keys: &str) -> FakeKeyboard {
+        FakeKeyboard {
+            keys: RefCell::new(
+                keys.chars()
+                    .map(|key| {
+                        if key == '\n' {
+                            Key::Enter
+                        } else {
+                            Key::Char(key)
+                        }
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    fn grid(text: &str) -> Grid {
+        Grid::from_str(text).unwrap()
+    }
+
+    #[test]
+    fn the_cursor_should_move_inside_the_grid() {
+        let mut editor = Editor::new(Grid::of_dead_cells(2, 3));
+
+        for command in [
+            EditorCommand::Up,
+            EditorCommand::Right,
+            EditorCommand::Right,
+            EditorCommand::Right,
+            EditorCommand::Down,
+            EditorCommand::Down,
+        ] {
+            editor.apply(command);
+        }
+
+        assert_eq!(editor.cursor(), Coordinates::new(1, 2));
+    }
+
+    #[test]
+    fn the_cell_under_the_cursor_should_be_toggled() {
+        let mut editor = Editor::new(Grid::of_dead_cells(2, 2));
+
+        editor.apply(EditorCommand::Right);
+        editor.apply(EditorCommand::Toggle);
+
+        assert_eq!(
+            editor.grid(),
+            &grid(
+                "⬛⬜
+                 ⬛⬛"
+            )
+        );
+
+        editor.apply(EditorCommand::Toggle);
+
+        assert_eq!(editor.grid().population(), 0);
+    }
+
+    #[test]
+    fn all_the_cells_should_be_filled_or_cleared_at_once() {
+        let mut editor = Editor::new(Grid::of_dead_cells(2, 2));
+
+        editor.apply(EditorCommand::Fill);
+        assert_eq!(editor.grid().population(), 4);
+
+        editor.apply(EditorCommand::Clear);
+        assert_eq!(editor.grid().population(), 0);
+    }
+
+    #[test]
+    fn the_cursor_should_stay_inside_the_grid_after_rotating_it() {
+        let mut editor = Editor::new(Grid::of_dead_cells(1, 3));
+        editor.apply(EditorCommand::Right);
+        editor.apply(EditorCommand::Right);
+
+        editor.apply(EditorCommand::Rotate);
+
+        assert_eq!(editor.grid().rows(), 3);
+        assert_eq!(editor.cursor(), Coordinates::new(0, 0));
+    }
+
+    #[test]
+    fn the_cell_under_the_cursor_should_be_highlighted() {
+        let editor = Editor::new(grid("⬜⬛"));
+
+        assert_eq!(
+            editor.render(&Alphabet::new('#', '.')),
+            "\x1B[7m#\x1B[27m.\n"
+        );
+    }
+
+    #[test]
+    fn it_should_run_the_edited_grid() {
+        let exit = edit(
+            Grid::of_dead_cells(1, 2),
+            &Alphabet::default(),
+            "pattern.txt",
+            |_| Ok(()),
+            &Logger::new(),
+            &pressing("lx\n"),
+        );
+
+        assert_eq!(exit, EditorExit::Run(grid("⬛⬜")));
+    }
+
+    #[test]
+    fn it_should_save_the_edited_grid() {
+        let saved = RefCell::new(None);
+
+        let exit = edit(
+            Grid::of_dead_cells(1, 2),
+            &Alphabet::default(),
+            "pattern.txt",
+            |grid| {
+                *saved.borrow_mut() = Some(grid.clone());
+                Ok(())
+            },
+            &Logger::new(),
+            &pressing("fsq"),
+        );
+
+        assert_eq!(exit, EditorExit::Quit);
+        assert_eq!(saved.into_inner(), Some(grid("⬜⬜")));
+    }
+
+    #[test]
+    fn it_should_tell_when_the_grid_could_not_be_saved() {
+        let console = Logger::new();
+
+        edit(
+            Grid::of_dead_cells(1, 1),
+            &Alphabet::default(),
+            "pattern.txt",
+            |_| Err(io::Error::other("disk full")),
+            &console,
+            &pressing("sq"),
+        );
+
+        assert!(console
+            .log()
+            .contains("Could not save to pattern.txt: disk full"));
+    }
+}
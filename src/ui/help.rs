@@ -4,13 +4,63 @@ pub fn print_usage() {
     eprintln!(
         "{} is an imaginary robot game (cellular automaton) made by the British mathematician John Horton Conway in 1970. 
         
-cargo run PATTERN_FILE_PATH ROWS COLUMNS GENERATIONS GENERATION_LIFETIME
+cargo run PATTERN_FILE_PATH [OPTIONS]
 
-PATTERN_FILE_PATH = The pattern file is a text file containing the pattern you want to use.
-ROWS = Number of rows for the background grid
-COLUMNS = Number of columns for the background grid
-GENERATIONS = Number of generations to run the game
-GENERATION_LIFETIME = Lifetime for a generation in seconds
+PATTERN_FILE_PATH = The pattern file is a text (.txt), plaintext (.cells), RLE (.rle) or Life 1.05/1.06 (.lif)
+                    file containing the pattern you want to use.
+
+OPTIONS:
+
+--rows=ROWS = Number of rows for the background grid (default: the pattern rows plus a margin)
+--cols=COLUMNS = Number of columns for the background grid (default: the pattern columns plus a margin)
+--generations=GENERATIONS = Number of generations to run the game (default: 1000)
+--delay=DELAY = Lifetime for a generation in seconds, like 1 or 0.5, or in milliseconds, like 250ms (default: 1)
+--fps=FPS = Generations shown per second. It is another way to set the generation lifetime
+--rule=RULE = Rule-string for the automaton, for example B36/S23 or 23/3
+              (default: the rule in the pattern file or B3/S23)
+--topology=TOPOLOGY = How the grid edges are stitched together: plane, torus, horizontal-cylinder,
+                      vertical-cylinder, klein-bottle or cross-surface (default: torus)
+--unbounded = Play on an unbounded universe. The background grid is only a window into it
+--threads=THREADS = Number of threads used to calculate the next generation (default: 1)
+--alphabet=CHARS = Live cell char followed by the dead cell char, for example #. or O.
+                   It is used to read text patterns and to display the grid (default: ⬜⬛)
+--anchor=ANCHOR = Where the pattern is placed on the grid: top-left, top, top-right, left, center,
+                  right, bottom-left, bottom or bottom-right (default: center)
+--offset=ROW,COLUMN = Place the pattern left top corner at the given row and column
+--until-stable = Stop when the pattern dies out, becomes a still life or oscillates.
+                 The exit code tells why: 10 extinction, 11 still life, 12 oscillation
+--interactive = Control the game with the keyboard: space pauses or resumes, n shows the next
+                generation while paused, + and - change the speed, r restarts and q quits
+--edit = Edit the pattern on the background grid before running the game
+--save-as=FILE = File where the editor saves the pattern, in the format for its extension
+                 (default: the pattern file)
+--full-redraw = Redraw the whole grid every generation. By default, only the cells that changed
+                are redrawn
+--render=MODE = How the cells are drawn: text (one alphabet char per cell), ascii (# and .),
+                half-block (two rows per char) or braille (four rows and two columns per char)
+                (default: text)
+--age-colours = Paint the live cells with a colour for their age, in the text and ascii modes
+--trail = Paint the live cells by age and the recently dead cells in fading greys
+--analyze = Tell if the last generation is a still life, an oscillator or a spaceship, with its
+            period and, for spaceships, its displacement and speed
+--stats=FILE = Save the live cells, births, deaths, bounding box and density of every generation,
+               as CSV or JSON for the file extension
+--status-line = Show the statistics of the generation under the grid
+--gif=FILE = Save the game as an animated GIF instead of showing it. It needs no terminal and
+             every frame is shown for the generation lifetime
+--no-loop = Play the animated GIF only once. By default, it loops forever
+--image=FILE = Save generations as numbered PNG or SVG images, for the file extension, instead of
+               showing the game. It needs no terminal
+--at-generation=N = Save only generation N as an image (default: 0, the pattern)
+--every=K = Save generation 0 and then every K generations as images
+--cell-size=PIXELS = Side of the square drawn for every cell in images, up to 100 (default: 4)
+--grid-lines = Draw a line around every cell in images
+--ruler = Number the rows and columns in images
+--live-colour=COLOUR = Colour of the live cells in images, like #ff8000 (default: #ffffff)
+--dead-colour=COLOUR = Colour of the dead cells in images (default: #000000)
+--line-colour=COLOUR = Colour of the grid lines and the ruler in images (default: #808080)
+-h, --help = Show this help
+-V, --version = Show the version
         
 For example, for the Glider pattern:
 
@@ -20,11 +70,19 @@ For example, for the Glider pattern:
 
 with a 30x60 background grid, you should run this command:
 
-cargo run ./patterns/glider.txt 30 60 1000 1
+cargo run ./patterns/glider.txt --rows=30 --cols=60 --generations=1000 --delay=1
 
 It will runt for 1000 generations and each generation lives for 1 second.
 
+To run the same pattern using the HighLife rule:
+
+cargo run ./patterns/glider.txt --rows=30 --cols=60 --rule=B36/S23
+
 ",
         "The Game of Life".green()
     );
 }
+
+pub fn print_version() {
+    println!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
+}
@@ -1,3 +1,3 @@
-
 pub mod help;
 pub mod console;
+pub mod editor;
@@ -1,6 +1,6 @@
 use std::fs;
 
-use game_of_life_kata::application::app;
+use game_of_life_kata::{application::app, domain::cell::alphabet::Alphabet};
 
 #[test]
 fn golden_master_test() {
@@ -12,15 +12,21 @@ fn golden_master_test() {
     const GENERATIONS: &str = "1";
     const GENERATION_LIFETIME: &str = "0";
 
-    let command =
-        format!("cargo run {PATTERN} {ROWS} {COLUMNS} {GENERATIONS} {GENERATION_LIFETIME}");
+    let command = format!(
+        "cargo run {PATTERN} --rows={ROWS} --cols={COLUMNS} --generations={GENERATIONS} --delay={GENERATION_LIFETIME}"
+    );
 
-    let final_state = app::run(&extract_args(&command));
+    let outcome = app::run(&extract_args(&command));
 
     let expected_final_state = fs::read_to_string("./tests/fixtures/expected_output.txt")
         .expect("test should have a fixture with the final game output");
 
-    assert_eq!(final_state, expected_final_state);
+    assert_eq!(outcome.generations, 1);
+    assert_eq!(outcome.population_history, vec![5]);
+    assert_eq!(
+        outcome.final_grid.to_string_with(&Alphabet::default()),
+        expected_final_state
+    );
 }
 
 /// Extract command arguments into a string vector